@@ -90,6 +90,7 @@ pub struct ScheduledTasksDTO {
     pub params: serde_json::Value,
     pub is_enabled: bool,
     pub retry_times: u8,
+    pub timezone: String,
     pub last_run: Option<chrono::NaiveDateTime>,
     pub next_run: Option<chrono::NaiveDateTime>,
     pub last_status: String,
@@ -106,6 +107,8 @@ pub struct CreateScheduledTaskDTO {
     pub is_enabled: bool,
     #[serde(default = "default_retry_times")]
     pub retry_times: u8,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
 }
 
 /// 更新定时任务的请求体（所有字段均可选）
@@ -116,6 +119,7 @@ pub struct UpdateScheduledTaskDTO {
     pub cron: Option<String>,
     pub params: Option<serde_json::Value>,
     pub retry_times: Option<u8>,
+    pub timezone: Option<String>,
 }
 
 /// 切换定时任务启停状态的请求体
@@ -125,10 +129,215 @@ pub struct ToggleScheduledTaskDTO {
     pub is_enabled: bool,
 }
 
+/// 定时任务执行记录 Response DTO
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTaskRunDTO {
+    pub id: i64,
+    pub task_id: i64,
+    pub task_name: String,
+    pub trigger_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub started_at: chrono::DateTime<Utc>,
+    pub finished_at: Option<chrono::DateTime<Utc>>,
+    pub error: Option<String>,
+    pub backoff_ms: i64,
+    pub item_count: i32,
+    pub message: Option<String>,
+}
+
+/// 写入一条定时任务执行记录
+#[derive(Debug, Clone)]
+pub struct NewScheduledTaskRunDTO {
+    pub task_name: String,
+    pub trigger_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub started_at: chrono::DateTime<Utc>,
+    pub finished_at: Option<chrono::DateTime<Utc>>,
+    pub error: Option<String>,
+    pub backoff_ms: i64,
+    pub item_count: i32,
+    pub message: Option<String>,
+}
+
+/// 写入一条健康检测记录
+#[derive(Debug, Clone)]
+pub struct NewHealthCheckResultDTO {
+    pub url: String,
+    pub success: bool,
+    pub status_code: Option<i32>,
+    pub latency_ms: i32,
+    pub error: Option<String>,
+}
+
+/// 单个地址的健康检测汇总 Response DTO
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckSummaryDTO {
+    pub url: String,
+    pub total_checks: i64,
+    pub success_checks: i64,
+    /// 可用率（百分比，保留两位小数）
+    pub uptime_percent: f64,
+    pub avg_latency_ms: Option<f64>,
+    pub max_latency_ms: Option<i32>,
+    pub last_success: bool,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub last_checked_at: chrono::DateTime<Utc>,
+}
+
+/// 新闻源登记 Response DTO
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsSourceDTO {
+    pub id: String,
+    pub display_name: String,
+    pub category: Option<String>,
+    pub enabled: bool,
+    pub fetch_kind: String,
+    pub url: Option<String>,
+    pub priority: i32,
+    pub language: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+/// 登记新闻源
+#[derive(Debug)]
+pub struct CreateNewsSourceDTO {
+    pub id: String,
+    pub display_name: String,
+    pub category: Option<String>,
+    pub enabled: bool,
+    pub fetch_kind: String,
+    pub url: Option<String>,
+    pub priority: i32,
+    pub language: String,
+}
+
+/// 更新新闻源（仅更新非 None 字段）
+#[derive(Debug)]
+pub struct UpdateNewsSourceDTO {
+    pub display_name: Option<String>,
+    pub category: Option<String>,
+    pub enabled: Option<bool>,
+    pub fetch_kind: Option<String>,
+    pub url: Option<String>,
+    pub priority: Option<i32>,
+    pub language: Option<String>,
+}
+
+/// 调度器主节点租约
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerLeaseDTO {
+    pub holder_id: String,
+    pub acquired_at: chrono::DateTime<Utc>,
+    pub renewed_at: chrono::DateTime<Utc>,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+/// 调度器状态 Response DTO
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerStatusDTO {
+    /// 当前响应请求的实例 ID
+    pub instance_id: String,
+    /// 当前实例是否为调度主节点
+    pub is_leader: bool,
+    /// 当前有效租约，无主节点时为 null
+    pub leader: Option<SchedulerLeaseDTO>,
+    /// 当前实例正在调度的任务数
+    pub scheduled_task_count: usize,
+}
+
+/// 用户 token 版本 Response DTO
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTokenVersionDTO {
+    pub user_id: i64,
+    /// 递增后的 token 版本，之前签发的 JWT 均已失效
+    pub token_version: i64,
+}
+
+/// 登录会话 Response DTO，一个会话对应同一次登录轮换出的全部 refresh_token
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSessionDTO {
+    /// 会话 ID（refresh_tokens.family_id）
+    pub id: i64,
+    /// 登录时间
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 最近一次刷新时间，未刷新过时与登录时间相同
+    pub last_refreshed_at: chrono::DateTime<chrono::Utc>,
+    /// 最近一次登录或刷新时的 User-Agent
+    pub user_agent: Option<String>,
+    /// 最近一次登录或刷新时的 IP
+    pub ip_address: Option<String>,
+    /// 会话最晚到期时间，到期后须重新登录
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// 是否为发起本次请求的会话
+    pub current: bool,
+}
+
+/// 定时任务命令参数定义 Response DTO
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCommandParamDTO {
+    /// params 中的字段名
+    pub name: String,
+    /// 取值类型：string / integer / boolean / url / url_list
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub required: bool,
+    pub description: String,
+}
+
+/// 定时任务命令 Response DTO
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCommandDTO {
+    /// params.cmd 的取值
+    pub name: String,
+    pub description: String,
+    /// 是否支持失败重试，不支持时任务的 retryTimes 只能为 0
+    pub retryable: bool,
+    pub params: Vec<TaskCommandParamDTO>,
+}
+
+/// 字段级校验错误
+#[derive(Debug, PartialEq, serde::Serialize)]
+pub struct FieldErrorDTO {
+    /// 出错的请求字段名
+    pub field: String,
+    pub message: String,
+}
+
+/// cron 触发时间预览 Response DTO
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTaskPreviewDTO {
+    /// 请求是否通过校验
+    pub valid: bool,
+    pub timezone: String,
+    /// 接下来的触发时间，带时区偏移
+    pub fire_times: Vec<chrono::DateTime<chrono::FixedOffset>>,
+    /// 合法但可能不符合预期的配置提示，例如触发过于频繁
+    pub warnings: Vec<String>,
+    pub errors: Vec<FieldErrorDTO>,
+}
+
 fn default_retry_times() -> u8 {
     3
 }
 
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
 /// 收藏番剧的请求体
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +26,23 @@ pub struct TaskFilter {
     pub is_enabled: Option<bool>,
 }
 
+/// 定义定时任务执行记录的查询参数结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTaskRunFilter {
+    pub status: Option<String>,
+    pub trigger_type: Option<String>,
+}
+
+/// 定义健康检测汇总的查询参数结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckFilter {
+    /// 统计最近多少小时的检测记录，默认 24
+    pub hours: Option<i64>,
+    pub url: Option<String>,
+}
+
 /// 定义番剧收藏的查询参数结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
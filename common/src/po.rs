@@ -93,6 +93,40 @@ pub struct UserInfo {
     pub failed_login_attempts: i64,
 }
 
+/// 鉴权中间件核对 JWT 时需要的账户状态
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct UserAuthState {
+    /// 与 JwtClaims.ver 比对，不一致说明 token 已被主动失效
+    pub token_version: i64,
+    /// 非 active 的账户拒绝访问
+    pub status: String,
+}
+
+/// 邮件中发送的一次性 token 的用途，对应 user_email_token.purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailTokenPurpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+impl EmailTokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailTokenPurpose::VerifyEmail => "verify_email",
+            EmailTokenPurpose::ResetPassword => "reset_password",
+        }
+    }
+}
+
+/// 发送验证 / 重置密码邮件时需要的用户信息
+#[derive(Debug, Clone, FromRow)]
+pub struct UserEmailTarget {
+    pub id: i64,
+    pub username: Option<String>,
+    pub email: String,
+    pub email_verified_at: Option<chrono::DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Rating {
     pub value: f64,               // 分数值
@@ -135,12 +169,15 @@ pub type ItemResult = HashMap<String, HashSet<TaskItem>>;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum TaskItem {
+    Ani(AniItem),
     News(NewsInfo),
     Health(HealthItem),
     ExtractNewsItem(HealthItem),
     ExtractNewsNewsKeywords(HealthItem),
     ExtractNewsEvent(HealthItem),
     MergeNewsItem(HealthItem),
+    FetchNews(HealthItem),
+    Maintenance(HealthItem),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, Hash, PartialEq)]
@@ -151,6 +188,84 @@ pub struct NewsInfo {
     pub items: Vec<Value>, // 不关心内部结构，直接用 Value 保存
 }
 
+/// 待抽取到 news_item 的新闻批次（news_info 中的一行）
+#[derive(Debug, Clone, FromRow)]
+pub struct NewsInfoBatch {
+    pub id: i64,
+    pub news_from: String,
+    pub name: Option<String>,
+    pub news_date: chrono::NaiveDate,
+    pub data: Option<Value>,
+}
+
+/// 提取关键词、聚类新闻事件使用的新闻条目文本
+#[derive(Debug, Clone, FromRow)]
+pub struct NewsItemText {
+    pub id: i64,
+    pub title: String,
+    pub content: Option<String>,
+    pub source: Option<String>,
+    pub extracted: bool,
+}
+
+/// 聚类生成的新闻事件，按 (event_date, cluster_id) 写入 news_event
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewNewsEvent {
+    pub event_date: chrono::NaiveDate,
+    pub cluster_id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub news_count: i32,
+    pub score: f32,
+}
+
+/// 参与跨天合并比较的新闻事件
+#[derive(Debug, Clone, FromRow)]
+pub struct NewsEventMergeCandidate {
+    pub id: i64,
+    pub event_date: chrono::NaiveDate,
+    pub title: Option<String>,
+    /// 0=自动生成，1=人工确认，2=已归档，3=已合并
+    pub status: i16,
+    pub parent_event_id: Option<i64>,
+}
+
+/// 源地址的抓取缓存（http_fetch_cache 中的一行）
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct HttpFetchCache {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub content_hash: Option<String>,
+    /// 上次写入 news_info 的日期
+    pub stored_date: Option<chrono::NaiveDate>,
+}
+
+/// 新闻事件流水线单个步骤的运行记录
+#[derive(Debug, Clone)]
+pub struct NewsEventPipelineRun {
+    pub step_name: String,
+    pub event_date: Option<chrono::NaiveDate>,
+    pub affected_rows: i32,
+    pub cost_ms: i32,
+    /// 0=成功，1=失败，2=部分成功，3=被跳过
+    pub status: i16,
+    pub message: Option<String>,
+}
+
+/// 番剧更新表抓取到的单集更新，按 (title, platform, update_count) 写入 ani_info
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AniItem {
+    pub title: String,
+    pub update_count: String,
+    pub update_info: String,
+    pub image_url: String,
+    pub detail_url: String,
+    pub update_time: chrono::DateTime<Utc>,
+    pub platform: String,
+}
+
 /// 健康检测返回的结果集
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, Hash, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -168,6 +283,7 @@ pub struct ScheduledTasks {
     pub params: Value,
     pub is_enabled: bool,
     pub retry_times: i16,
+    pub timezone: String,
     pub last_run: Option<NaiveDateTime>,
     pub next_run: Option<NaiveDateTime>,
     pub last_status: String,
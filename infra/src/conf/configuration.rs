@@ -13,6 +13,8 @@ pub struct Setting {
     pub application: ApplicationSettings,
     pub email_client: EmailClientSettings,
     pub token: TokenConfig,
+    #[serde(default)]
+    pub login_lockout: LoginLockoutSettings,
     pub github_client_id: Option<String>,
     pub github_client_secret: Option<Secret<String>>,
     pub oauth_base_url: Option<String>,
@@ -20,12 +22,55 @@ pub struct Setting {
     pub is_production: bool,
 }
 
+impl Setting {
+    /// 校验需要由部署环境提供、没有可用默认值的配置
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        if self.application.frontend_base_url.trim().is_empty() {
+            return Err(config::ConfigError::Message(
+                "application.frontend_base_url 未配置，请通过 APP_APPLICATION__FRONTEND_BASE_URL 设置"
+                    .into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 ///---------------------- 各类配置项 ----------------------
 #[derive(serde::Deserialize, Clone, Debug)]
 pub struct EmailClientSettings {
     pub base_url: String,
     pub sender_email: String,
     pub authorization_token: Secret<String>,
+    /// 请求邮件服务的超时时间（毫秒）
+    #[serde(
+        default = "default_email_timeout_milliseconds",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub timeout_milliseconds: u64,
+}
+
+fn default_email_timeout_milliseconds() -> u64 {
+    10_000
+}
+
+/// 登录失败锁定策略
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct LoginLockoutSettings {
+    /// 连续登录失败达到该次数后锁定账户
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_failed_attempts: i32,
+    /// 锁定时长（分钟）
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lockout_minutes: i64,
+}
+
+impl Default for LoginLockoutSettings {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lockout_minutes: 15,
+        }
+    }
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
@@ -33,6 +78,8 @@ pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
+    /// 前端地址，用于拼接邮件中的验证 / 重置密码链接
+    pub frontend_base_url: String,
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
@@ -165,6 +212,29 @@ mod tests {
         assert_eq!(config.token["refresh_token"], 15);
     }
 
+    #[test]
+    fn test_email_client_config() {
+        let config = get_configuration(Some(PathBuf::from("../configuration"))).unwrap();
+        assert_eq!(config.email_client.timeout_milliseconds, 10_000);
+    }
+
+    #[test]
+    fn test_validate_requires_frontend_base_url() {
+        let mut config = get_configuration(Some(PathBuf::from("../configuration"))).unwrap();
+        config.application.frontend_base_url = " ".into();
+        assert!(config.validate().is_err());
+
+        config.application.frontend_base_url = "https://agora.example.com".into();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_login_lockout_config() {
+        let config = get_configuration(Some(PathBuf::from("../configuration"))).unwrap();
+        assert_eq!(config.login_lockout.max_failed_attempts, 5);
+        assert_eq!(config.login_lockout.lockout_minutes, 15);
+    }
+
     #[test]
     fn test_database_settings_env_overrides() {
         let origin_settings = get_configuration(Some(PathBuf::from("../configuration"))).unwrap();
@@ -5,9 +5,46 @@ use chrono_tz::Asia::Shanghai;
 use common::AniFilter;
 use common::api::ApiError;
 use common::dto::AniInfoDto;
-use common::po::{AniInfo, PageData, QueryPage};
+use common::po::{AniInfo, AniItem, PageData, QueryPage};
 use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 
+/// 写入番剧更新，同一番剧同一集重复抓取时更新其余字段
+pub async fn upsert_ani_info(ani: &AniItem, db_pool: &PgPool) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO ani_info (
+            title,
+            update_count,
+            update_info,
+            image_url,
+            detail_url,
+            update_time,
+            platform
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (title, platform, update_count) DO UPDATE SET
+            update_info = EXCLUDED.update_info,
+            image_url = EXCLUDED.image_url,
+            detail_url = EXCLUDED.detail_url,
+            update_time = EXCLUDED.update_time
+        "#,
+    )
+    .bind(&ani.title)
+    .bind(&ani.update_count)
+    .bind(&ani.update_info)
+    .bind(&ani.image_url)
+    .bind(&ani.detail_url)
+    .bind(ani.update_time)
+    .bind(&ani.platform)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("插入或更新 ani_info {:?} 失败: {}", ani, e);
+        anyhow::anyhow!(e)
+    })?;
+
+    Ok(())
+}
+
 /// 根据 id 查询单条
 pub async fn get_ani_info_by_id(id: i64, db_pool: &PgPool) -> Result<Option<AniInfoDto>> {
     let rec = sqlx::query_as::<_, AniInfo>(
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use common::dto::{HealthCheckSummaryDTO, NewHealthCheckResultDTO};
+use sqlx::PgPool;
+
+/// 写入一条健康检测记录
+pub async fn insert_health_check_result(
+    record: &NewHealthCheckResultDTO,
+    db_pool: &PgPool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO health_check_result (url, success, status_code, latency_ms, error)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(&record.url)
+    .bind(record.success)
+    .bind(record.status_code)
+    .bind(record.latency_ms)
+    .bind(&record.error)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("写入健康检测记录 {} 失败: {e:?}", record.url);
+        anyhow::anyhow!("写入健康检测记录失败")
+    })?;
+
+    Ok(())
+}
+
+/// 按地址汇总 since 之后的健康检测记录，按地址排序
+///
+/// url 不为空时只统计该地址
+pub async fn list_health_check_summaries(
+    since: chrono::DateTime<chrono::Utc>,
+    url: Option<&str>,
+    db_pool: &PgPool,
+) -> Result<Vec<HealthCheckSummaryDTO>> {
+    let rows: Vec<HealthCheckSummaryDTO> = sqlx::query_as(
+        r#"
+        SELECT
+            r.url,
+            COUNT(*) AS total_checks,
+            COUNT(*) FILTER (WHERE r.success) AS success_checks,
+            ROUND(100.0 * COUNT(*) FILTER (WHERE r.success) / COUNT(*), 2)::FLOAT8 AS uptime_percent,
+            AVG(r.latency_ms)::FLOAT8 AS avg_latency_ms,
+            MAX(r.latency_ms) AS max_latency_ms,
+            last.success AS last_success,
+            last.status_code AS last_status_code,
+            last.error AS last_error,
+            last.checked_at AS last_checked_at
+        FROM health_check_result r
+        CROSS JOIN LATERAL (
+            SELECT success, status_code, error, checked_at
+            FROM health_check_result l
+            WHERE l.url = r.url
+            ORDER BY checked_at DESC, id DESC
+            LIMIT 1
+        ) last
+        WHERE r.checked_at >= $1
+          AND ($2::TEXT IS NULL OR r.url = $2)
+        GROUP BY r.url, last.success, last.status_code, last.error, last.checked_at
+        ORDER BY r.url
+        "#,
+    )
+    .bind(since)
+    .bind(url)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("汇总健康检测记录失败: {e:?}");
+        anyhow::anyhow!("汇总健康检测记录失败")
+    })?;
+
+    Ok(rows)
+}
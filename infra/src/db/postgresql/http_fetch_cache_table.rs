@@ -0,0 +1,55 @@
+use anyhow::Result;
+use common::po::HttpFetchCache;
+use sqlx::PgPool;
+
+/// 批量查询源地址的抓取缓存
+pub async fn list_http_fetch_cache(
+    urls: &[String],
+    db_pool: &PgPool,
+) -> Result<Vec<HttpFetchCache>> {
+    let rows: Vec<HttpFetchCache> = sqlx::query_as(
+        r#"
+        SELECT url, etag, last_modified, content_hash, stored_date
+        FROM http_fetch_cache
+        WHERE url = ANY($1)
+        "#,
+    )
+    .bind(urls)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询抓取缓存失败: {e:?}");
+        anyhow::anyhow!("查询抓取缓存失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 写入或更新源地址的抓取缓存，同时刷新抓取时间
+pub async fn upsert_http_fetch_cache(cache: &HttpFetchCache, db_pool: &PgPool) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO http_fetch_cache (url, etag, last_modified, content_hash, stored_date, fetched_at)
+        VALUES ($1, $2, $3, $4, $5, CURRENT_TIMESTAMP)
+        ON CONFLICT (url) DO UPDATE SET
+            etag = EXCLUDED.etag,
+            last_modified = EXCLUDED.last_modified,
+            content_hash = EXCLUDED.content_hash,
+            stored_date = EXCLUDED.stored_date,
+            fetched_at = EXCLUDED.fetched_at
+        "#,
+    )
+    .bind(&cache.url)
+    .bind(&cache.etag)
+    .bind(&cache.last_modified)
+    .bind(&cache.content_hash)
+    .bind(cache.stored_date)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("更新 {} 的抓取缓存失败: {e:?}", cache.url);
+        anyhow::anyhow!("更新抓取缓存失败")
+    })?;
+
+    Ok(())
+}
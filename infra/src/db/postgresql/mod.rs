@@ -1,15 +1,33 @@
 mod ani_collect_table;
 mod ani_info_table;
 mod common;
+mod health_check_result_table;
+mod http_fetch_cache_table;
+mod news_event_table;
 mod news_info_table;
 mod news_item_table;
+mod news_keywords_table;
+mod news_source_table;
+mod refresh_token_table;
+mod scheduled_task_run_table;
 mod scheduled_tasks;
+mod scheduler_leader_table;
+mod user_email_token_table;
 mod user_info_table;
 
 pub use ani_collect_table::*;
 pub use ani_info_table::*;
 pub use common::*;
+pub use health_check_result_table::*;
+pub use http_fetch_cache_table::*;
+pub use news_event_table::*;
 pub use news_info_table::*;
 pub use news_item_table::*;
+pub use news_keywords_table::*;
+pub use news_source_table::*;
+pub use refresh_token_table::*;
+pub use scheduled_task_run_table::*;
 pub use scheduled_tasks::*;
+pub use scheduler_leader_table::*;
+pub use user_email_token_table::*;
 pub use user_info_table::*;
@@ -0,0 +1,275 @@
+use anyhow::Result;
+use common::po::{NewNewsEvent, NewsEventMergeCandidate, NewsEventPipelineRun, NewsItemText};
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// 查询某一天的新闻条目文本，按 id 顺序
+pub async fn list_news_item_texts_by_date(
+    event_date: chrono::NaiveDate,
+    db_pool: &PgPool,
+) -> Result<Vec<NewsItemText>> {
+    let rows: Vec<NewsItemText> = sqlx::query_as(
+        r#"
+        SELECT id, title, content, source, extracted
+        FROM news_item
+        WHERE published_at = $1
+        ORDER BY id
+        "#,
+    )
+    .bind(event_date)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询 {event_date} 的新闻条目失败: {e:?}");
+        anyhow::anyhow!("查询新闻条目失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 写入或更新聚类事件，返回事件 id
+pub async fn upsert_news_event(
+    event: &NewNewsEvent,
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO news_event (event_date, cluster_id, title, summary, news_count, score)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (event_date, cluster_id) DO UPDATE SET
+            title = EXCLUDED.title,
+            summary = EXCLUDED.summary,
+            news_count = EXCLUDED.news_count,
+            score = EXCLUDED.score
+        RETURNING id
+        "#,
+    )
+    .bind(event.event_date)
+    .bind(event.cluster_id)
+    .bind(&event.title)
+    .bind(&event.summary)
+    .bind(event.news_count)
+    .bind(event.score)
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(|e| {
+        tracing::error!("写入新闻事件 {:?} 失败: {e}", event);
+        e
+    })
+}
+
+/// 用本次聚类结果替换事件关联的新闻条目，并回写条目的 cluster_id
+pub async fn replace_news_event_items(
+    event_id: i64,
+    cluster_id: i64,
+    cluster_method: &str,
+    news_ids: &[i64],
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM news_event_item WHERE event_id = $1")
+        .bind(event_id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO news_event_item (event_id, news_id)
+        SELECT $1, UNNEST($2::BIGINT[])
+        "#,
+    )
+    .bind(event_id)
+    .bind(news_ids)
+    .execute(tx.as_mut())
+    .await?;
+    sqlx::query(
+        r#"
+        UPDATE news_item SET cluster_id = $1, cluster_method = $2 WHERE id = ANY($3)
+        "#,
+    )
+    .bind(cluster_id)
+    .bind(cluster_method)
+    .bind(news_ids)
+    .execute(tx.as_mut())
+    .await
+    .map_err(|e| {
+        tracing::error!("更新新闻事件 {event_id} 的关联条目失败: {e}");
+        e
+    })?;
+
+    Ok(())
+}
+
+/// 清空某一天新闻条目的聚类结果，重新聚类前调用
+pub async fn reset_news_item_clusters(
+    event_date: chrono::NaiveDate,
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE news_item SET cluster_id = NULL, cluster_method = NULL
+        WHERE published_at = $1 AND cluster_id IS NOT NULL
+        "#,
+    )
+    .bind(event_date)
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// 删除某一天不再出现在聚类结果中的自动事件；已人工确认或参与过跨天合并的事件保留
+pub async fn delete_stale_news_events(
+    event_date: chrono::NaiveDate,
+    cluster_ids: &[i64],
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM news_event e
+        WHERE e.event_date = $1
+          AND e.status = 0
+          AND e.parent_event_id IS NULL
+          AND e.cluster_id <> ALL($2)
+          AND NOT EXISTS (SELECT 1 FROM news_event c WHERE c.parent_event_id = e.id)
+        "#,
+    )
+    .bind(event_date)
+    .bind(cluster_ids)
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// 查询日期区间内（含两端）的新闻事件，按日期、id 排序
+pub async fn list_news_events_between(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    db_pool: &PgPool,
+) -> Result<Vec<NewsEventMergeCandidate>> {
+    let rows: Vec<NewsEventMergeCandidate> = sqlx::query_as(
+        r#"
+        SELECT id, event_date, title, status, parent_event_id
+        FROM news_event
+        WHERE event_date BETWEEN $1 AND $2
+        ORDER BY event_date, id
+        "#,
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询 {start_date} ~ {end_date} 的新闻事件失败: {e:?}");
+        anyhow::anyhow!("查询新闻事件失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 查询事件成员新闻的链接，返回 (event_id, url)
+pub async fn list_news_event_member_urls(
+    event_ids: &[i64],
+    db_pool: &PgPool,
+) -> Result<Vec<(i64, String)>> {
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT ei.event_id, ni.url
+        FROM news_event_item ei
+        JOIN news_item ni ON ni.id = ei.news_id
+        WHERE ei.event_id = ANY($1)
+        "#,
+    )
+    .bind(event_ids)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询新闻事件成员失败: {e:?}");
+        anyhow::anyhow!("查询新闻事件成员失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 按事件汇总成员新闻的关键词权重，返回 (event_id, keyword, weight)
+pub async fn list_news_event_keywords(
+    event_ids: &[i64],
+    db_pool: &PgPool,
+) -> Result<Vec<(i64, String, f64)>> {
+    let rows: Vec<(i64, String, f64)> = sqlx::query_as(
+        r#"
+        SELECT ei.event_id, nk.keyword, SUM(COALESCE(nk.weight, 0))::FLOAT8
+        FROM news_event_item ei
+        JOIN news_keywords nk ON nk.news_id = ei.news_id
+        WHERE ei.event_id = ANY($1)
+        GROUP BY ei.event_id, nk.keyword
+        "#,
+    )
+    .bind(event_ids)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询新闻事件关键词失败: {e:?}");
+        anyhow::anyhow!("查询新闻事件关键词失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 把子事件挂到父事件下并标记为已合并，子事件原有的子事件一并改挂到父事件
+pub async fn merge_news_event(
+    child_id: i64,
+    parent_id: i64,
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE news_event SET parent_event_id = $2, status = 3, merge_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+    )
+    .bind(child_id)
+    .bind(parent_id)
+    .execute(tx.as_mut())
+    .await?;
+    sqlx::query("UPDATE news_event SET parent_event_id = $2 WHERE parent_event_id = $1")
+        .bind(child_id)
+        .bind(parent_id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("UPDATE news_event SET merge_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(parent_id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| {
+            tracing::error!("合并新闻事件 {child_id} -> {parent_id} 失败: {e}");
+            e
+        })?;
+
+    Ok(())
+}
+
+/// 写入事件流水线运行记录
+pub async fn insert_news_event_pipeline_run(
+    run: &NewsEventPipelineRun,
+    db_pool: &PgPool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO news_event_pipeline_run (step_name, event_date, affected_rows, cost_ms, status, message)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&run.step_name)
+    .bind(run.event_date)
+    .bind(run.affected_rows)
+    .bind(run.cost_ms)
+    .bind(run.status)
+    .bind(&run.message)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("写入事件流水线记录 {:?} 失败: {e:?}", run);
+        anyhow::anyhow!("写入事件流水线记录失败")
+    })?;
+
+    Ok(())
+}
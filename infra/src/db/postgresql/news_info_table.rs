@@ -2,18 +2,21 @@ use actix_web::web;
 use anyhow::Result;
 use chrono::Utc;
 use common::NewsFilter;
-use common::api::{ApiError, NewsInfo2Item};
+use common::api::ApiError;
 use common::dto::NewsInfoDTO;
-use common::po::{NewsInfo, PageData, QueryPage};
+use common::po::{NewsInfo, NewsInfoBatch, PageData, QueryPage};
 use serde_json::json;
 use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 
 /// 新闻信息插入新记录
-pub async fn upsert_news_info(news_item: &NewsInfo, db_pool: &PgPool) -> Result<()> {
+///
+/// 同一来源同一天重复抓取时覆盖原始数据；数据有变化时重置抽取状态，新增条目会在下次抽取时写入 news_item。
+/// 数据和名称都没有变化时不改写（不触发 updated_at），返回是否有写入
+pub async fn upsert_news_info(news_item: &NewsInfo, db_pool: &PgPool) -> Result<bool> {
     let data = json!({
         "items": news_item.items
     });
-    let _ = sqlx::query(
+    let result = sqlx::query(
         r#"
         INSERT INTO public.news_info (
             news_from,
@@ -23,7 +26,17 @@ pub async fn upsert_news_info(news_item: &NewsInfo, db_pool: &PgPool) -> Result<
         ON CONFLICT (news_from, news_date) DO UPDATE SET
             news_from = EXCLUDED.news_from,
             name = EXCLUDED.name,
-            data = EXCLUDED.data
+            data = EXCLUDED.data,
+            extracted = news_info.extracted AND news_info.data IS NOT DISTINCT FROM EXCLUDED.data,
+            extracted_at = CASE
+                WHEN news_info.data IS NOT DISTINCT FROM EXCLUDED.data THEN news_info.extracted_at
+            END,
+            extract_attempts = CASE
+                WHEN news_info.data IS NOT DISTINCT FROM EXCLUDED.data THEN news_info.extract_attempts
+                ELSE 0
+            END
+        WHERE news_info.data IS DISTINCT FROM EXCLUDED.data
+           OR news_info.name IS DISTINCT FROM EXCLUDED.name
         "#,
     )
     .bind(&news_item.id)
@@ -36,7 +49,7 @@ pub async fn upsert_news_info(news_item: &NewsInfo, db_pool: &PgPool) -> Result<
         anyhow::anyhow!(e)
     })?;
 
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
 #[derive(Debug, FromRow, Clone)]
@@ -157,22 +170,90 @@ pub async fn list_all_news_info_by_page(
     Ok(result)
 }
 
+/// 数据库当前日期，即写入 news_info 时 news_date 默认取到的日期（按数据库会话时区）
+pub async fn current_news_date(db_pool: &PgPool) -> Result<chrono::NaiveDate> {
+    sqlx::query_scalar("SELECT CURRENT_DATE")
+        .fetch_one(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("查询数据库当前日期失败: {e:?}");
+            anyhow::anyhow!("查询数据库当前日期失败")
+        })
+}
+
+/// 查询待抽取的新闻批次，按 id 顺序，上次抽取失败的批次排在最后；失败次数达到 max_attempts 的批次不再返回
+pub async fn list_unextracted_news_info(
+    limit: i64,
+    max_attempts: i32,
+    db_pool: &PgPool,
+) -> Result<Vec<NewsInfoBatch>> {
+    let rows: Vec<NewsInfoBatch> = sqlx::query_as(
+        r#"
+        SELECT id, news_from, name, news_date, data
+        FROM news_info
+        WHERE extracted IS NOT TRUE
+          AND extract_attempts < $2
+        ORDER BY (error IS NOT NULL), id
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .bind(max_attempts)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询待抽取的 news_info 失败: {e:?}");
+        anyhow::anyhow!("查询待抽取的 news_info 失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 标记新闻批次抽取完成，并清空上次的错误信息
 pub async fn upsert_news_info_extracted_state(
-    news_item: &NewsInfo2Item,
+    news_info_id: i64,
     tx: &mut sqlx::Transaction<'_, Postgres>,
 ) -> Result<(), sqlx::Error> {
     sqlx::query(
         r#"
-            update news_info set extracted = true, extracted_at = CURRENT_TIMESTAMP where id = $1
+        UPDATE news_info
+        SET extracted = true, extracted_at = CURRENT_TIMESTAMP, error = NULL, extract_attempts = 0
+        WHERE id = $1
         "#,
     )
-    .bind(news_item.id)
+    .bind(news_info_id)
     .execute(tx.as_mut())
     .await
     .map_err(|e| {
-        tracing::error!("更新news_info表 {:?} 数据提取状态失败: {}", news_item, e);
+        tracing::error!("更新 news_info {news_info_id} 抽取状态失败: {e}");
         e
     })?;
 
     Ok(())
 }
+
+/// 记录新闻批次的抽取失败原因并累加失败次数，返回累计失败次数；批次保持未抽取状态
+pub async fn record_news_info_extract_error(
+    news_info_id: i64,
+    error: &str,
+    db_pool: &PgPool,
+) -> Result<i32> {
+    let attempts: i32 = sqlx::query_scalar(
+        r#"
+        UPDATE news_info
+        SET error = $2, extract_attempts = extract_attempts + 1
+        WHERE id = $1
+        RETURNING extract_attempts
+        "#,
+    )
+    .bind(news_info_id)
+    .bind(error)
+    .fetch_one(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("记录 news_info {news_info_id} 抽取错误失败: {e:?}");
+        anyhow::anyhow!("记录 news_info 抽取错误失败")
+    })?;
+
+    Ok(attempts)
+}
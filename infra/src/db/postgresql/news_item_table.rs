@@ -2,7 +2,7 @@ use actix_web::web;
 use anyhow::Result;
 use common::api::{ApiError, NewsInfo2Item};
 use common::dto::{NewsEventDTO, NewsItemResponseDTO};
-use common::po::{PageData, QueryPage};
+use common::po::{NewsItemText, PageData, QueryPage};
 use common::{NewsEventFilter, NewsItemFilter};
 use sqlx::{PgPool, QueryBuilder};
 
@@ -47,6 +47,51 @@ pub async fn upsert_news_item(
     Ok(())
 }
 
+/// 查询最近写入的新闻条目文本，按 id 倒序
+pub async fn list_recent_news_item_texts(
+    limit: i64,
+    db_pool: &PgPool,
+) -> Result<Vec<NewsItemText>> {
+    let rows: Vec<NewsItemText> = sqlx::query_as(
+        r#"
+        SELECT id, title, content, source, extracted
+        FROM news_item
+        ORDER BY id DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询新闻条目文本失败: {e:?}");
+        anyhow::anyhow!("查询新闻条目文本失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 标记新闻条目已完成关键词提取
+pub async fn mark_news_item_extracted(
+    news_id: i64,
+    tx: &mut sqlx::Transaction<'_, sqlx::postgres::Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE news_item SET extracted = true, extracted_at = CURRENT_TIMESTAMP WHERE id = $1
+        "#,
+    )
+    .bind(news_id)
+    .execute(tx.as_mut())
+    .await
+    .map_err(|e| {
+        tracing::error!("更新 news_item {news_id} 提取状态失败: {e}");
+        e
+    })?;
+
+    Ok(())
+}
+
 /// 分页查询新闻条目列表
 pub async fn list_all_news_item_by_page(
     query: web::Query<QueryPage<NewsItemFilter>>,
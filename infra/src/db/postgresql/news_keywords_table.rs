@@ -0,0 +1,30 @@
+/// 写入新闻条目的关键词，同一条目同一方法下的关键词重复写入时更新权重
+pub async fn upsert_news_keywords(
+    news_id: i64,
+    keywords: &[(String, f64)],
+    method: &str,
+    tx: &mut sqlx::Transaction<'_, sqlx::postgres::Postgres>,
+) -> Result<(), sqlx::Error> {
+    for (keyword, weight) in keywords {
+        sqlx::query(
+            r#"
+            INSERT INTO news_keywords (news_id, keyword, weight, method)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (news_id, keyword, method) DO UPDATE SET
+                weight = EXCLUDED.weight
+            "#,
+        )
+        .bind(news_id)
+        .bind(keyword)
+        .bind(*weight as f32)
+        .bind(method)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| {
+            tracing::error!("写入 news_item {news_id} 关键词 {keyword} 失败: {e}");
+            e
+        })?;
+    }
+
+    Ok(())
+}
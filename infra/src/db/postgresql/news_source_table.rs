@@ -0,0 +1,154 @@
+use anyhow::Result;
+use common::dto::{CreateNewsSourceDTO, NewsSourceDTO, UpdateNewsSourceDTO};
+use sqlx::PgPool;
+
+const NEWS_SOURCE_COLUMNS: &str = "id, display_name, category, enabled, fetch_kind, url, priority, language, created_at, updated_at";
+
+/// 查询全部新闻源，按优先级从高到低、id 排序
+pub async fn list_news_sources(db_pool: &PgPool) -> Result<Vec<NewsSourceDTO>> {
+    let rows: Vec<NewsSourceDTO> = sqlx::query_as(&format!(
+        "SELECT {NEWS_SOURCE_COLUMNS} FROM news_source ORDER BY priority DESC, id"
+    ))
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询新闻源失败: {e:?}");
+        anyhow::anyhow!("查询新闻源失败")
+    })?;
+
+    Ok(rows)
+}
+
+/// 按 id 查询新闻源
+pub async fn get_news_source(id: &str, db_pool: &PgPool) -> Result<Option<NewsSourceDTO>> {
+    let row: Option<NewsSourceDTO> = sqlx::query_as(&format!(
+        "SELECT {NEWS_SOURCE_COLUMNS} FROM news_source WHERE id = $1"
+    ))
+    .bind(id)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询新闻源 {id} 失败: {e:?}");
+        anyhow::anyhow!("查询新闻源失败")
+    })?;
+
+    Ok(row)
+}
+
+/// 登记新闻源；id 已存在时返回 None
+pub async fn create_news_source(
+    data: &CreateNewsSourceDTO,
+    db_pool: &PgPool,
+) -> Result<Option<NewsSourceDTO>> {
+    let row: Option<NewsSourceDTO> = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO news_source (id, display_name, category, enabled, fetch_kind, url, priority, language)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (id) DO NOTHING
+        RETURNING {NEWS_SOURCE_COLUMNS}
+        "#
+    ))
+    .bind(&data.id)
+    .bind(&data.display_name)
+    .bind(&data.category)
+    .bind(data.enabled)
+    .bind(&data.fetch_kind)
+    .bind(&data.url)
+    .bind(data.priority)
+    .bind(&data.language)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("登记新闻源 {} 失败: {e:?}", data.id);
+        anyhow::anyhow!("登记新闻源失败")
+    })?;
+
+    Ok(row)
+}
+
+/// 更新新闻源（仅更新非 None 字段）
+pub async fn update_news_source(
+    id: &str,
+    data: &UpdateNewsSourceDTO,
+    db_pool: &PgPool,
+) -> Result<NewsSourceDTO> {
+    let row: NewsSourceDTO = sqlx::query_as(&format!(
+        r#"
+        UPDATE news_source
+        SET
+            display_name = COALESCE($2, display_name),
+            category     = COALESCE($3, category),
+            enabled      = COALESCE($4, enabled),
+            fetch_kind   = COALESCE($5, fetch_kind),
+            url          = COALESCE($6, url),
+            priority     = COALESCE($7, priority),
+            language     = COALESCE($8, language)
+        WHERE id = $1
+        RETURNING {NEWS_SOURCE_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(&data.display_name)
+    .bind(&data.category)
+    .bind(data.enabled)
+    .bind(&data.fetch_kind)
+    .bind(&data.url)
+    .bind(data.priority)
+    .bind(&data.language)
+    .fetch_one(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("更新新闻源 {id} 失败: {e:?}");
+        anyhow::anyhow!("更新新闻源失败或新闻源不存在")
+    })?;
+
+    Ok(row)
+}
+
+/// 删除新闻源登记；已抓取的 news_info 不受影响，下次抓取时会重新自动登记
+pub async fn delete_news_source(id: &str, db_pool: &PgPool) -> Result<()> {
+    let rows_affected = sqlx::query("DELETE FROM news_source WHERE id = $1")
+        .bind(id)
+        .execute(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("删除新闻源 {id} 失败: {e:?}");
+            anyhow::anyhow!("删除新闻源失败")
+        })?
+        .rows_affected();
+
+    if rows_affected == 0 {
+        return Err(anyhow::anyhow!("新闻源不存在"));
+    }
+
+    Ok(())
+}
+
+/// 抓取时自动登记未知来源，已登记的不做改动
+pub async fn register_news_source(
+    id: &str,
+    display_name: &str,
+    fetch_kind: &str,
+    url: &str,
+    db_pool: &PgPool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO news_source (id, display_name, fetch_kind, url)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(id)
+    .bind(display_name)
+    .bind(fetch_kind)
+    .bind(url)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("自动登记新闻源 {id} 失败: {e:?}");
+        anyhow::anyhow!("自动登记新闻源失败")
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use common::dto::UserSessionDTO;
+use sqlx::PgPool;
+
+/// 查询用户仍然有效的登录会话，按最近刷新时间倒序
+///
+/// current_token 为本次请求携带的 refresh_token，用于标记当前会话
+pub async fn list_user_sessions(
+    user_id: i64,
+    current_token: Option<&str>,
+    db_pool: &PgPool,
+) -> Result<Vec<UserSessionDTO>> {
+    let sessions: Vec<UserSessionDTO> = sqlx::query_as(
+        r#"
+        SELECT family_id                                            AS id,
+               MIN(created_at)                                      AS created_at,
+               MAX(created_at)                                      AS last_refreshed_at,
+               (ARRAY_AGG(user_agent ORDER BY created_at DESC))[1]  AS user_agent,
+               (ARRAY_AGG(ip_address ORDER BY created_at DESC))[1]  AS ip_address,
+               MAX(session_expires_at)                              AS expires_at,
+               COALESCE(BOOL_OR(token = $2), false)                 AS current
+        FROM refresh_tokens
+        WHERE user_id = $1
+        GROUP BY family_id
+        HAVING BOOL_OR(NOT revoked AND expires_at > now() AND session_expires_at > now())
+        ORDER BY MAX(created_at) DESC
+        "#,
+    )
+    .bind(user_id)
+    .bind(current_token)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询用户 {user_id} 的登录会话失败: {e:?}");
+        anyhow::anyhow!("查询登录会话失败")
+    })?;
+
+    Ok(sessions)
+}
+
+/// 吊销用户的某个登录会话，返回是否存在未吊销的 token
+pub async fn revoke_user_session(user_id: i64, family_id: i64, db_pool: &PgPool) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked = true
+        WHERE user_id = $1
+          AND family_id = $2
+          AND revoked = false
+        "#,
+    )
+    .bind(user_id)
+    .bind(family_id)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("吊销用户 {user_id} 的会话 {family_id} 失败: {e:?}");
+        anyhow::anyhow!("吊销登录会话失败")
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// 删除过期超过 retention_days 天的 refresh_token，返回删除行数
+///
+/// 已吊销但未过期的 token 保留，用于识别被重放的旧 token
+pub async fn delete_expired_refresh_tokens(retention_days: i64, db_pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        DELETE
+        FROM refresh_tokens
+        WHERE expires_at < now() - make_interval(days => $1::int)
+           OR session_expires_at < now() - make_interval(days => $1::int)
+        "#,
+    )
+    .bind(retention_days)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("清理过期 refresh_token 失败: {e:?}");
+        anyhow::anyhow!("清理过期 refresh_token 失败")
+    })?;
+
+    Ok(result.rows_affected())
+}
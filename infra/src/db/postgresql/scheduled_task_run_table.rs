@@ -0,0 +1,201 @@
+use actix_web::web;
+use anyhow::Result;
+use common::ScheduledTaskRunFilter;
+use common::api::ApiError;
+use common::dto::{NewScheduledTaskRunDTO, ScheduledTaskRunDTO};
+use common::po::{PageData, QueryPage};
+use sqlx::{PgPool, QueryBuilder};
+
+/// 写入一条定时任务执行记录，返回记录 id
+///
+/// task_id 按任务名称关联；任务已被删除时不写入，返回 None
+pub async fn insert_scheduled_task_run(
+    run: &NewScheduledTaskRunDTO,
+    db_pool: &PgPool,
+) -> Result<Option<i64>> {
+    let id: Option<i64> = sqlx::query_scalar(
+        r#"
+        INSERT INTO scheduled_task_run (
+            task_id, task_name, trigger_type, status, attempts,
+            started_at, finished_at, error, backoff_ms, item_count, message
+        )
+        SELECT id, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+        FROM scheduled_tasks
+        WHERE name = $1
+        RETURNING id
+        "#,
+    )
+    .bind(&run.task_name)
+    .bind(&run.trigger_type)
+    .bind(&run.status)
+    .bind(run.attempts)
+    .bind(run.started_at)
+    .bind(run.finished_at)
+    .bind(&run.error)
+    .bind(run.backoff_ms)
+    .bind(run.item_count)
+    .bind(&run.message)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("写入任务执行记录失败 [{}]: {e:?}", run.task_name);
+        anyhow::anyhow!("写入任务执行记录失败")
+    })?;
+
+    Ok(id)
+}
+
+/// 回填预先创建的执行记录（手动触发时先写入 running 状态，执行结束后更新）
+pub async fn finish_scheduled_task_run(
+    id: i64,
+    run: &NewScheduledTaskRunDTO,
+    db_pool: &PgPool,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        r#"
+        UPDATE scheduled_task_run
+        SET status      = $2,
+            attempts    = $3,
+            finished_at = $4,
+            error       = $5,
+            backoff_ms  = $6,
+            item_count  = $7,
+            message     = $8
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&run.status)
+    .bind(run.attempts)
+    .bind(run.finished_at)
+    .bind(&run.error)
+    .bind(run.backoff_ms)
+    .bind(run.item_count)
+    .bind(&run.message)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("更新任务执行记录 {id} 失败: {e:?}");
+        anyhow::anyhow!("更新任务执行记录失败")
+    })?
+    .rows_affected();
+
+    if rows_affected == 0 {
+        return Err(anyhow::anyhow!("执行记录不存在"));
+    }
+    Ok(())
+}
+
+/// 查询某个定时任务的单条执行记录
+pub async fn get_scheduled_task_run(
+    task_id: i64,
+    run_id: i64,
+    db_pool: &PgPool,
+) -> Result<Option<ScheduledTaskRunDTO>> {
+    let row: Option<ScheduledTaskRunDTO> = sqlx::query_as(
+        r#"
+        SELECT id, task_id, task_name, trigger_type, status, attempts,
+               started_at, finished_at, error, backoff_ms, item_count, message
+        FROM scheduled_task_run
+        WHERE id = $1 AND task_id = $2
+        "#,
+    )
+    .bind(run_id)
+    .bind(task_id)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询任务执行记录 {run_id} 失败: {e:?}");
+        anyhow::anyhow!("查询任务执行记录失败")
+    })?;
+
+    Ok(row)
+}
+
+/// 分页查询某个定时任务的执行记录，按开始时间倒序
+pub async fn list_scheduled_task_runs_by_page(
+    task_id: i64,
+    query: web::Query<QueryPage<ScheduledTaskRunFilter>>,
+    db_pool: &PgPool,
+) -> Result<PageData<ScheduledTaskRunDTO>> {
+    let mut result = PageData {
+        items: vec![],
+        total_count: 0,
+        page: 1,
+        page_size: 20,
+        total_pages: 0,
+    };
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Row {
+        #[sqlx(flatten)]
+        pub run: ScheduledTaskRunDTO,
+        pub total_count: i64,
+    }
+
+    let mut query_builder: QueryBuilder<sqlx::postgres::Postgres> = QueryBuilder::new(
+        r#"
+            SELECT
+                id,
+                task_id,
+                task_name,
+                trigger_type,
+                status,
+                attempts,
+                started_at,
+                finished_at,
+                error,
+                backoff_ms,
+                item_count,
+                message,
+                COUNT(*) OVER() AS total_count
+            FROM scheduled_task_run
+            WHERE task_id =
+        "#,
+    );
+    query_builder.push_bind(task_id);
+
+    if let Some(filter) = &query.filter {
+        if let Some(status) = &filter.status {
+            query_builder.push(" AND status = ");
+            query_builder.push_bind(status);
+        }
+        if let Some(trigger_type) = &filter.trigger_type {
+            query_builder.push(" AND trigger_type = ");
+            query_builder.push_bind(trigger_type);
+        }
+    }
+
+    query_builder.push(" ORDER BY started_at DESC, id DESC ");
+
+    let page_size = query.page_size.unwrap_or(20);
+    let page = query.page.unwrap_or(1).max(1);
+
+    query_builder.push(" LIMIT ");
+    query_builder.push_bind(page_size as i64);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(((page - 1) * page_size) as i64);
+
+    result.page = page;
+    result.page_size = page_size;
+
+    let rows: Vec<Row> = query_builder
+        .build_query_as()
+        .fetch_all(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("查询任务执行记录失败: {e:?}");
+            ApiError::Database("数据库查询失败".into())
+        })?;
+
+    let total = rows.first().map(|r| r.total_count).unwrap_or(0);
+    result.total_count = total as usize;
+    result.total_pages = if page_size > 0 {
+        ((total as f64) / (page_size as f64)).ceil() as u32
+    } else {
+        0
+    };
+    result.items = rows.into_iter().map(|r| r.run).collect();
+
+    Ok(result)
+}
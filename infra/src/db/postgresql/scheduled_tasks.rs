@@ -17,6 +17,7 @@ struct ScheduledTasksWithTotal {
     pub params: serde_json::Value,
     pub is_enabled: bool,
     pub retry_times: i16,
+    pub timezone: String,
     pub last_run: Option<NaiveDateTime>,
     pub next_run: Option<NaiveDateTime>,
     pub last_status: String,
@@ -35,7 +36,7 @@ fn normalize_retry_times(retry_times: i16) -> anyhow::Result<u8> {
 pub async fn list_all_scheduled_tasks(db_pool: &PgPool) -> anyhow::Result<Vec<ScheduledTasksDTO>> {
     let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
         r#"
-            SELECT id, name, cron, params, is_enabled, retry_times, last_run, next_run, last_status, created_at, updated_at
+            SELECT id, name, cron, params, is_enabled, retry_times, timezone, last_run, next_run, last_status, created_at, updated_at
             FROM scheduled_tasks
           "#,
     );
@@ -60,6 +61,7 @@ pub async fn list_all_scheduled_tasks(db_pool: &PgPool) -> anyhow::Result<Vec<Sc
                 params: task.params.clone(),
                 is_enabled: task.is_enabled,
                 retry_times: normalize_retry_times(task.retry_times)?,
+                timezone: task.timezone.clone(),
                 last_run: task.last_run,
                 next_run: task.next_run,
                 last_status: task.last_status.clone(),
@@ -84,7 +86,7 @@ pub async fn list_all_scheduled_tasks_by_page(
 
     let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
         r#"
-            SELECT id, name, cron, params, is_enabled, retry_times, last_run, next_run, last_status, created_at, updated_at, COUNT(*) OVER() as total_count
+            SELECT id, name, cron, params, is_enabled, retry_times, timezone, last_run, next_run, last_status, created_at, updated_at, COUNT(*) OVER() as total_count
             FROM scheduled_tasks
             WHERE 1 = 1
           "#,
@@ -145,6 +147,7 @@ pub async fn list_all_scheduled_tasks_by_page(
                 params: task.params.clone(),
                 is_enabled: task.is_enabled,
                 retry_times: normalize_retry_times(task.retry_times)?,
+                timezone: task.timezone.clone(),
                 last_run: task.last_run,
                 next_run: task.next_run,
                 last_status: task.last_status.clone(),
@@ -171,6 +174,43 @@ pub async fn list_all_scheduled_tasks_by_page(
     Ok(result)
 }
 
+/// 按 id 查询单个定时任务
+pub async fn get_scheduled_task_by_id(
+    id: i64,
+    db_pool: &PgPool,
+) -> anyhow::Result<Option<ScheduledTasksDTO>> {
+    let row: Option<ScheduledTasks> = sqlx::query_as(
+        r#"
+        SELECT id, name, cron, params, is_enabled, retry_times, timezone, last_run, next_run, last_status, created_at, updated_at
+        FROM scheduled_tasks
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询定时任务 {id} 失败: {e:?}");
+        anyhow::anyhow!("查询定时任务失败")
+    })?;
+
+    row.map(|row| {
+        Ok(ScheduledTasksDTO {
+            id: row.id,
+            name: row.name,
+            cron: row.cron,
+            params: row.params,
+            is_enabled: row.is_enabled,
+            retry_times: normalize_retry_times(row.retry_times)?,
+            timezone: row.timezone,
+            last_run: row.last_run,
+            next_run: row.next_run,
+            last_status: row.last_status,
+        })
+    })
+    .transpose()
+}
+
 /// 创建新的定时任务
 pub async fn create_scheduled_task(
     data: &CreateScheduledTaskDTO,
@@ -178,9 +218,9 @@ pub async fn create_scheduled_task(
 ) -> anyhow::Result<ScheduledTasksDTO> {
     let row: ScheduledTasks = sqlx::query_as(
         r#"
-        INSERT INTO scheduled_tasks (name, cron, params, is_enabled, retry_times, last_status)
-        VALUES ($1, $2, $3, $4, $5, 'pending')
-        RETURNING id, name, cron, params, is_enabled, retry_times, last_run, next_run, last_status, created_at, updated_at
+        INSERT INTO scheduled_tasks (name, cron, params, is_enabled, retry_times, timezone, last_status)
+        VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+        RETURNING id, name, cron, params, is_enabled, retry_times, timezone, last_run, next_run, last_status, created_at, updated_at
         "#,
     )
     .bind(&data.name)
@@ -188,6 +228,7 @@ pub async fn create_scheduled_task(
     .bind(&data.params)
     .bind(data.is_enabled)
     .bind(data.retry_times as i16)
+    .bind(&data.timezone)
     .fetch_one(db_pool)
     .await
     .map_err(|e| {
@@ -202,6 +243,7 @@ pub async fn create_scheduled_task(
         params: row.params,
         is_enabled: row.is_enabled,
         retry_times: normalize_retry_times(row.retry_times)?,
+        timezone: row.timezone,
         last_run: row.last_run,
         next_run: row.next_run,
         last_status: row.last_status,
@@ -222,9 +264,10 @@ pub async fn update_scheduled_task(
             cron        = COALESCE($3, cron),
             params      = COALESCE($4, params),
             retry_times = COALESCE($5, retry_times),
+            timezone    = COALESCE($6, timezone),
             updated_at  = NOW()
         WHERE id = $1
-        RETURNING id, name, cron, params, is_enabled, retry_times, last_run, next_run, last_status, created_at, updated_at
+        RETURNING id, name, cron, params, is_enabled, retry_times, timezone, last_run, next_run, last_status, created_at, updated_at
         "#,
     )
     .bind(id)
@@ -232,6 +275,7 @@ pub async fn update_scheduled_task(
     .bind(&data.cron)
     .bind(&data.params)
     .bind(data.retry_times.map(|r| r as i16))
+    .bind(&data.timezone)
     .fetch_one(db_pool)
     .await
     .map_err(|e| {
@@ -246,6 +290,7 @@ pub async fn update_scheduled_task(
         params: row.params,
         is_enabled: row.is_enabled,
         retry_times: normalize_retry_times(row.retry_times)?,
+        timezone: row.timezone,
         last_run: row.last_run,
         next_run: row.next_run,
         last_status: row.last_status,
@@ -335,6 +380,24 @@ pub async fn update_scheduled_task_runtime(
     Ok(())
 }
 
+/// 仅推进任务的下次运行时间（补跑错过的触发后使用，避免重启时重复补跑）
+pub async fn update_scheduled_task_next_run(
+    name: &str,
+    next_run: Option<chrono::DateTime<Utc>>,
+    db_pool: &PgPool,
+) -> anyhow::Result<()> {
+    sqlx::query(r#"UPDATE scheduled_tasks SET next_run = $2 WHERE name = $1"#)
+        .bind(name)
+        .bind(next_run.map(|dt| dt.naive_utc()))
+        .execute(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("更新任务下次运行时间失败 [{name}]: {e:?}");
+            anyhow::anyhow!("更新任务下次运行时间失败")
+        })?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::normalize_retry_times;
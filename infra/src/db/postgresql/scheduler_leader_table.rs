@@ -0,0 +1,97 @@
+use anyhow::Result;
+use common::dto::SchedulerLeaseDTO;
+use sqlx::PgPool;
+
+/// 定时任务配置变更通知频道，非主节点修改配置后通过它通知主节点重载
+pub const SCHEDULED_TASKS_CHANGED_CHANNEL: &str = "scheduled_tasks_changed";
+
+/// 尝试获取或续约调度器租约，返回当前实例是否持有租约
+///
+/// 租约不存在、已过期或本就由 holder_id 持有时获取成功
+pub async fn try_acquire_scheduler_lease(
+    name: &str,
+    holder_id: &str,
+    ttl_secs: f64,
+    db_pool: &PgPool,
+) -> Result<bool> {
+    let holder: Option<String> = sqlx::query_scalar(
+        r#"
+        INSERT INTO scheduler_leader (name, holder_id, acquired_at, renewed_at, expires_at)
+        VALUES ($1, $2, now(), now(), now() + make_interval(secs => $3))
+        ON CONFLICT (name) DO UPDATE
+        SET holder_id   = EXCLUDED.holder_id,
+            acquired_at = CASE
+                              WHEN scheduler_leader.holder_id = EXCLUDED.holder_id
+                                  THEN scheduler_leader.acquired_at
+                              ELSE now()
+                          END,
+            renewed_at  = now(),
+            expires_at  = EXCLUDED.expires_at
+        WHERE scheduler_leader.holder_id = EXCLUDED.holder_id
+           OR scheduler_leader.expires_at < now()
+        RETURNING holder_id
+        "#,
+    )
+    .bind(name)
+    .bind(holder_id)
+    .bind(ttl_secs)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("获取调度器租约失败 [{name}]: {e:?}");
+        anyhow::anyhow!("获取调度器租约失败")
+    })?;
+
+    Ok(holder.is_some())
+}
+
+/// 主动释放租约（仅当租约由 holder_id 持有时生效）
+pub async fn release_scheduler_lease(name: &str, holder_id: &str, db_pool: &PgPool) -> Result<()> {
+    sqlx::query(r#"DELETE FROM scheduler_leader WHERE name = $1 AND holder_id = $2"#)
+        .bind(name)
+        .bind(holder_id)
+        .execute(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("释放调度器租约失败 [{name}]: {e:?}");
+            anyhow::anyhow!("释放调度器租约失败")
+        })?;
+    Ok(())
+}
+
+/// 查询当前有效的租约，已过期视为无主节点
+pub async fn get_scheduler_lease(
+    name: &str,
+    db_pool: &PgPool,
+) -> Result<Option<SchedulerLeaseDTO>> {
+    let row: Option<SchedulerLeaseDTO> = sqlx::query_as(
+        r#"
+        SELECT holder_id, acquired_at, renewed_at, expires_at
+        FROM scheduler_leader
+        WHERE name = $1 AND expires_at >= now()
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询调度器租约失败 [{name}]: {e:?}");
+        anyhow::anyhow!("查询调度器租约失败")
+    })?;
+
+    Ok(row)
+}
+
+/// 广播定时任务配置已变更
+pub async fn notify_scheduled_tasks_changed(payload: &str, db_pool: &PgPool) -> Result<()> {
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(SCHEDULED_TASKS_CHANGED_CHANNEL)
+        .bind(payload)
+        .execute(db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("发送定时任务变更通知失败: {e:?}");
+            anyhow::anyhow!("发送定时任务变更通知失败")
+        })?;
+    Ok(())
+}
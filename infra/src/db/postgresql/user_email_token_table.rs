@@ -0,0 +1,224 @@
+use anyhow::Result;
+use common::po::{EmailTokenPurpose, UserEmailTarget};
+use sqlx::PgPool;
+
+/// 按用户 ID 或邮箱查询可以接收邮件的 active 用户（须已填写邮箱）
+pub async fn find_user_email_target(
+    user_id: Option<i64>,
+    email: Option<&str>,
+    db_pool: &PgPool,
+) -> Result<Option<UserEmailTarget>> {
+    let target = sqlx::query_as::<_, UserEmailTarget>(
+        r#"
+        SELECT id, username, email, email_verified_at
+        FROM user_info
+        WHERE ($1::bigint IS NULL OR id = $1)
+          AND ($2::text IS NULL OR email = $2)
+          AND email IS NOT NULL
+          AND status = 'active'
+        ORDER BY id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(email)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询邮件接收用户失败: {e:?}");
+        anyhow::anyhow!("查询用户失败")
+    })?;
+
+    Ok(target)
+}
+
+/// 为用户签发新的一次性 token，同用途下尚未使用的旧 token 一并作废
+///
+/// cooldown_secs 内已签发过同用途 token 时不签发并返回 false，避免频繁发信
+pub async fn create_user_email_token(
+    user_id: i64,
+    purpose: EmailTokenPurpose,
+    token_hash: &str,
+    email: &str,
+    ttl_minutes: i64,
+    cooldown_secs: i64,
+    db_pool: &PgPool,
+) -> Result<bool> {
+    let mut tx = db_pool.begin().await?;
+
+    let recently_sent: bool = sqlx::query_scalar(
+        r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM user_email_token
+                WHERE user_id = $1
+                  AND purpose = $2
+                  AND created_at > now() - make_interval(secs => $3::double precision)
+            )
+        "#,
+    )
+    .bind(user_id)
+    .bind(purpose.as_str())
+    .bind(cooldown_secs)
+    .fetch_one(&mut *tx)
+    .await?;
+    if recently_sent {
+        tx.rollback().await?;
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+            DELETE FROM user_email_token
+            WHERE user_id = $1
+              AND purpose = $2
+              AND used_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(purpose.as_str())
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+            INSERT INTO user_email_token (user_id, purpose, token_hash, email, expires_at)
+            VALUES ($1, $2, $3, $4, now() + make_interval(mins => $5::int))
+        "#,
+    )
+    .bind(user_id)
+    .bind(purpose.as_str())
+    .bind(token_hash)
+    .bind(email)
+    .bind(ttl_minutes)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// 消费一个未使用、未过期的 token，返回其所属用户和签发时的邮箱
+async fn consume_user_email_token(
+    token_hash: &str,
+    purpose: EmailTokenPurpose,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<(i64, String)>> {
+    let consumed: Option<(i64, String)> = sqlx::query_as(
+        r#"
+            UPDATE user_email_token
+            SET used_at = now()
+            WHERE token_hash = $1
+              AND purpose = $2
+              AND used_at IS NULL
+              AND expires_at > now()
+            RETURNING user_id, email
+        "#,
+    )
+    .bind(token_hash)
+    .bind(purpose.as_str())
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(consumed)
+}
+
+/// 用验证 token 标记邮箱已验证，返回用户 ID
+///
+/// token 无效、已使用、已过期，或用户邮箱已变更时返回 None
+pub async fn verify_email_with_token(token_hash: &str, db_pool: &PgPool) -> Result<Option<i64>> {
+    let mut tx = db_pool.begin().await?;
+
+    let Some((user_id, email)) =
+        consume_user_email_token(token_hash, EmailTokenPurpose::VerifyEmail, &mut tx).await?
+    else {
+        tx.rollback().await?;
+        return Ok(None);
+    };
+
+    let updated = sqlx::query(
+        r#"
+            UPDATE user_info
+            SET email_verified_at = COALESCE(email_verified_at, now()),
+                updated_at        = now()
+            WHERE id = $1
+              AND email = $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(&email)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok((updated.rows_affected() > 0).then_some(user_id))
+}
+
+/// 用重置 token 设置新密码，返回用户 ID
+///
+/// 同时解除登录锁定、递增 token 版本并吊销全部 refresh_token，使旧会话全部下线；
+/// token 无效、已使用、已过期，或用户邮箱已变更时返回 None
+pub async fn reset_password_with_token(
+    token_hash: &str,
+    password_hash: &str,
+    db_pool: &PgPool,
+) -> Result<Option<i64>> {
+    let mut tx = db_pool.begin().await?;
+
+    let Some((user_id, email)) =
+        consume_user_email_token(token_hash, EmailTokenPurpose::ResetPassword, &mut tx).await?
+    else {
+        tx.rollback().await?;
+        return Ok(None);
+    };
+
+    let updated = sqlx::query(
+        r#"
+            UPDATE user_info
+            SET password              = $3,
+                token_version         = token_version + 1,
+                failed_login_attempts = 0,
+                locked_until          = NULL,
+                updated_at            = now()
+            WHERE id = $1
+              AND email = $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(&email)
+    .bind(password_hash)
+    .execute(&mut *tx)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        tx.commit().await?;
+        return Ok(None);
+    }
+
+    sqlx::query(
+        r#"
+            UPDATE refresh_tokens
+            SET revoked = true
+            WHERE user_id = $1 AND revoked = false
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+            DELETE FROM user_email_token
+            WHERE user_id = $1
+              AND purpose = $2
+              AND used_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(EmailTokenPurpose::ResetPassword.as_str())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(user_id))
+}
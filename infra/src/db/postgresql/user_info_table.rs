@@ -1,7 +1,7 @@
 use chrono::Utc;
 use chrono_tz::Asia::Shanghai;
 use common::dto::{NewUser, UserDto, UserIdentityDto};
-use common::po::UserInfo;
+use common::po::{UserAuthState, UserInfo};
 use serde::Deserialize;
 use sqlx::FromRow;
 use sqlx::PgPool;
@@ -57,6 +57,154 @@ pub async fn get_user_by_email(email: String, db_pool: &PgPool) -> anyhow::Resul
     Ok(dto)
 }
 
+/// 查询用户的 token 版本和账户状态，用户不存在时返回 None
+pub async fn get_user_auth_state(
+    user_id: i64,
+    db_pool: &PgPool,
+) -> anyhow::Result<Option<UserAuthState>> {
+    let state = sqlx::query_as::<_, UserAuthState>(
+        r#"
+            SELECT token_version, status
+            FROM user_info
+            WHERE id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询用户 {user_id} 的账户状态失败: {e:?}");
+        anyhow::anyhow!("查询用户账户状态失败")
+    })?;
+
+    Ok(state)
+}
+
+/// 查询用户的有效权限：所属角色的权限与所在套餐的权限之并集
+pub async fn list_user_permissions(user_id: i64, db_pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    let permissions: Vec<String> = sqlx::query_scalar(
+        r#"
+            SELECT DISTINCT p.name
+            FROM permissions p
+            LEFT JOIN role_permissions rp ON rp.permission_id = p.id
+            LEFT JOIN user_roles ur ON ur.role_id = rp.role_id AND ur.user_id = $1
+            LEFT JOIN plan_permissions pp ON pp.permission_id = p.id
+            LEFT JOIN user_info ui ON ui.id = $1 AND ui.plan = pp.plan
+            WHERE ur.user_id IS NOT NULL OR ui.id IS NOT NULL
+            ORDER BY p.name
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("查询用户 {user_id} 的权限失败: {e:?}");
+        anyhow::anyhow!("查询用户权限失败")
+    })?;
+
+    Ok(permissions)
+}
+
+/// 递增用户的 token 版本并吊销其全部 refresh_token，使该用户在所有设备上下线
+///
+/// 返回递增后的版本号，用户不存在时返回 None
+pub async fn bump_user_token_version(
+    user_id: i64,
+    db_pool: &PgPool,
+) -> anyhow::Result<Option<i64>> {
+    let mut tx = db_pool.begin().await?;
+
+    let version: Option<i64> = sqlx::query_scalar(
+        r#"
+            UPDATE user_info
+            SET token_version = token_version + 1,
+                updated_at    = now()
+            WHERE id = $1
+            RETURNING token_version
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if version.is_some() {
+        sqlx::query(
+            r#"
+                UPDATE refresh_tokens
+                SET revoked = true
+                WHERE user_id = $1 AND revoked = false
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(version)
+}
+
+/// 记录一次登录失败：原子地递增失败次数，达到阈值时锁定账户并把计数清零
+///
+/// 返回本次更新后的锁定到期时间，未锁定时为 None
+pub async fn record_login_failure(
+    user_id: i64,
+    max_failed_attempts: i32,
+    lockout_minutes: i64,
+    db_pool: &PgPool,
+) -> anyhow::Result<Option<chrono::DateTime<Utc>>> {
+    let locked_until: Option<chrono::DateTime<Utc>> = sqlx::query_scalar(
+        r#"
+            UPDATE user_info
+            SET failed_login_attempts = CASE
+                                            WHEN COALESCE(failed_login_attempts, 0) + 1 >= $2 THEN 0
+                                            ELSE COALESCE(failed_login_attempts, 0) + 1
+                                        END,
+                locked_until          = CASE
+                                            WHEN COALESCE(failed_login_attempts, 0) + 1 >= $2
+                                                THEN now() + make_interval(mins => $3::int)
+                                            ELSE locked_until
+                                        END
+            WHERE id = $1
+            RETURNING locked_until
+        "#,
+    )
+    .bind(user_id)
+    .bind(max_failed_attempts)
+    .bind(lockout_minutes)
+    .fetch_one(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("记录用户 {user_id} 登录失败次数失败: {e:?}");
+        anyhow::anyhow!("记录登录失败次数失败")
+    })?;
+
+    Ok(locked_until.filter(|until| *until > Utc::now()))
+}
+
+/// 清除登录失败次数和锁定状态，登录成功或管理员解锁时调用
+///
+/// 返回用户是否存在
+pub async fn reset_login_failures(user_id: i64, db_pool: &PgPool) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        r#"
+            UPDATE user_info
+            SET failed_login_attempts = 0,
+                locked_until          = NULL
+            WHERE id = $1
+        "#,
+    )
+    .bind(user_id)
+    .execute(db_pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("清除用户 {user_id} 登录锁定状态失败: {e:?}");
+        anyhow::anyhow!("清除登录锁定状态失败")
+    })?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// 新增用户
 pub async fn insert_users(users: &[NewUser], pool: &PgPool) -> anyhow::Result<()> {
     if users.is_empty() {
@@ -0,0 +1,173 @@
+use crate::EmailClientSettings;
+use anyhow::Result;
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
+use std::time::Duration;
+
+/// 通过 HTTP 邮件 API（Postmark 兼容）发送邮件
+///
+/// 请求 `POST {base_url}/email`，用 `X-Postmark-Server-Token` 头携带授权 token
+#[derive(Clone, Debug)]
+pub struct EmailClient {
+    http_client: Client,
+    base_url: String,
+    sender: String,
+    authorization_token: Secret<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SendEmailRequest<'a> {
+    from: &'a str,
+    to: &'a str,
+    subject: &'a str,
+    html_body: &'a str,
+    text_body: &'a str,
+}
+
+impl EmailClient {
+    pub fn new(
+        base_url: String,
+        sender: String,
+        authorization_token: Secret<String>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let http_client = Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            http_client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            sender,
+            authorization_token,
+        })
+    }
+
+    pub fn from_settings(settings: &EmailClientSettings) -> Result<Self> {
+        Self::new(
+            settings.base_url.clone(),
+            settings.sender_email.clone(),
+            settings.authorization_token.clone(),
+            Duration::from_millis(settings.timeout_milliseconds),
+        )
+    }
+
+    /// 发送一封邮件，邮件服务返回非 2xx 时视为失败
+    pub async fn send_email(
+        &self,
+        recipient: &str,
+        subject: &str,
+        html_content: &str,
+        text_content: &str,
+    ) -> Result<()> {
+        let body = SendEmailRequest {
+            from: &self.sender,
+            to: recipient,
+            subject,
+            html_body: html_content,
+            text_body: text_content,
+        };
+
+        self.http_client
+            .post(format!("{}/email", self.base_url))
+            .header(
+                "X-Postmark-Server-Token",
+                self.authorization_token.expose_secret(),
+            )
+            .json(&body)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|e| {
+                tracing::error!("发送邮件到 {recipient} 失败: {e:?}");
+                anyhow::anyhow!("发送邮件失败")
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+    use serde_json::Value;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    type Received = Arc<Mutex<Vec<(Option<String>, Value)>>>;
+
+    /// 在本地随机端口启动模拟邮件服务，记录收到的请求并返回 status
+    fn mock_mail_server(status: u16) -> (String, Received) {
+        let received: Received = Arc::default();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let state = received.clone();
+        let server = HttpServer::new(move || {
+            let state = state.clone();
+            App::new().route(
+                "/email",
+                web::post().to(move |req: HttpRequest, body: web::Json<Value>| {
+                    let state = state.clone();
+                    async move {
+                        let token = req
+                            .headers()
+                            .get("X-Postmark-Server-Token")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        state.lock().unwrap().push((token, body.into_inner()));
+                        HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap())
+                            .finish()
+                    }
+                }),
+            )
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap()
+        .run();
+        actix_web::rt::spawn(server);
+
+        (format!("http://127.0.0.1:{port}"), received)
+    }
+
+    fn email_client(base_url: String) -> EmailClient {
+        EmailClient::new(
+            base_url,
+            "noreply@example.com".into(),
+            Secret::new("my-secret-token".into()),
+            Duration::from_secs(5),
+        )
+        .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn send_email_posts_message_with_token_header() {
+        let (base_url, received) = mock_mail_server(200);
+
+        email_client(format!("{base_url}/"))
+            .send_email("alice@example.com", "主题", "<p>正文</p>", "正文")
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (token, body) = &received[0];
+        assert_eq!(token.as_deref(), Some("my-secret-token"));
+        assert_eq!(body["From"], "noreply@example.com");
+        assert_eq!(body["To"], "alice@example.com");
+        assert_eq!(body["Subject"], "主题");
+        assert_eq!(body["HtmlBody"], "<p>正文</p>");
+        assert_eq!(body["TextBody"], "正文");
+    }
+
+    #[actix_web::test]
+    async fn send_email_fails_on_error_status() {
+        let (base_url, _) = mock_mail_server(500);
+
+        let result = email_client(base_url)
+            .send_email("alice@example.com", "主题", "<p>正文</p>", "正文")
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,2 @@
+mod email_client;
+pub use email_client::*;
@@ -5,3 +5,6 @@ pub use log::*;
 
 mod db;
 pub use db::*;
+
+mod email;
+pub use email::*;
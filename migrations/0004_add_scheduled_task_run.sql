@@ -0,0 +1,39 @@
+-- ============================================================
+-- Table: scheduled_task_run（定时任务执行历史）
+-- 每次任务执行（含重试）记录一行，scheduled_tasks 中只保留最近一次运行态
+-- ============================================================
+CREATE TABLE IF NOT EXISTS scheduled_task_run
+(
+    id           BIGSERIAL PRIMARY KEY,
+    task_id      BIGINT       NOT NULL REFERENCES scheduled_tasks (id) ON DELETE CASCADE,
+    task_name    VARCHAR(128) NOT NULL,
+    trigger_type VARCHAR(16)  NOT NULL DEFAULT 'cron',
+    status       VARCHAR(16)  NOT NULL DEFAULT 'running',
+    attempts     INTEGER      NOT NULL DEFAULT 0,
+    started_at   TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    finished_at  TIMESTAMPTZ,
+    error        TEXT,
+    backoff_ms   BIGINT       NOT NULL DEFAULT 0,
+    item_count   INTEGER      NOT NULL DEFAULT 0,
+    message      TEXT,
+    created_at   TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    updated_at   TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE scheduled_task_run IS '定时任务执行历史，每次执行（含重试）一条记录';
+COMMENT ON COLUMN scheduled_task_run.trigger_type IS '触发方式：cron 等';
+COMMENT ON COLUMN scheduled_task_run.status IS '执行结果：running、success、failed 等';
+COMMENT ON COLUMN scheduled_task_run.attempts IS '本次执行实际尝试次数（首次 + 重试）';
+COMMENT ON COLUMN scheduled_task_run.error IS '各次失败尝试的错误信息，按尝试顺序换行拼接';
+COMMENT ON COLUMN scheduled_task_run.backoff_ms IS '重试退避累计等待时长（毫秒）';
+COMMENT ON COLUMN scheduled_task_run.item_count IS '本次执行产出的结果条目数';
+COMMENT ON COLUMN scheduled_task_run.message IS '任务返回的附加说明';
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_task_run_task_started
+    ON scheduled_task_run (task_id, started_at DESC);
+
+CREATE TRIGGER scheduled_task_run_updated_at_trg
+    BEFORE UPDATE
+    ON scheduled_task_run
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
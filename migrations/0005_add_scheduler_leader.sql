@@ -0,0 +1,19 @@
+-- ============================================================
+-- Table: scheduler_leader（调度器主节点租约）
+-- 多实例部署时仅持有未过期租约的实例运行定时任务调度器
+-- ============================================================
+CREATE TABLE IF NOT EXISTS scheduler_leader
+(
+    name        VARCHAR(64) PRIMARY KEY,
+    holder_id   VARCHAR(128) NOT NULL,
+    acquired_at TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    renewed_at  TIMESTAMPTZ  NOT NULL DEFAULT now(),
+    expires_at  TIMESTAMPTZ  NOT NULL
+);
+
+COMMENT ON TABLE scheduler_leader IS '调度器主节点租约，每种租约一行';
+COMMENT ON COLUMN scheduler_leader.name IS '租约名称，如 task_scheduler';
+COMMENT ON COLUMN scheduler_leader.holder_id IS '当前持有租约的实例 ID';
+COMMENT ON COLUMN scheduler_leader.acquired_at IS '当前持有者取得租约的时间';
+COMMENT ON COLUMN scheduler_leader.renewed_at IS '最近一次续约时间';
+COMMENT ON COLUMN scheduler_leader.expires_at IS '租约过期时间，过期后其他实例可接管';
@@ -0,0 +1,5 @@
+-- 每日合并新闻事件任务：服务在触发时间停机时，重启后补跑一次，避免当天的合并步骤被跳过
+UPDATE scheduled_tasks
+SET params = params || '{"misfire_policy": "run_once"}'::jsonb
+WHERE name = '合并新闻事件'
+  AND NOT params ? 'misfire_policy';
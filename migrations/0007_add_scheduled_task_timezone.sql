@@ -0,0 +1,14 @@
+-- ============================================================
+-- scheduled_tasks.timezone（cron 表达式所在时区）
+-- cron 各字段按该时区的本地时间解释，last_run / next_run 仍以 UTC 存储
+-- ============================================================
+ALTER TABLE scheduled_tasks
+    ADD COLUMN IF NOT EXISTS timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
+
+COMMENT ON COLUMN scheduled_tasks.timezone IS 'cron 表达式所在时区（IANA 时区名），默认 UTC';
+
+-- 内置的新闻处理任务按北京时间编写（如 6-23,0-1 点），此前按 UTC 调度导致时段偏移
+UPDATE scheduled_tasks
+SET timezone = 'Asia/Shanghai'
+WHERE name IN ('提取news_info到news_item', '提取新闻关键字到news_keywords', '提取新闻事件', '合并新闻事件')
+  AND timezone = 'UTC';
@@ -0,0 +1,8 @@
+-- ============================================================
+-- 新闻条目抽取改为本地执行（native_extract_news_item）
+-- 不再调用外部分析服务，保留任务上已有的执行控制参数
+-- ============================================================
+UPDATE scheduled_tasks
+SET params = (params - 'arg' - 'url') || '{"cmd": "native_extract_news_item"}'::jsonb
+WHERE name = '提取news_info到news_item'
+  AND params ->> 'cmd' = 'extract_transform_news_info_to_item';
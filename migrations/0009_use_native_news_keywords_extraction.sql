@@ -0,0 +1,9 @@
+-- ============================================================
+-- 新闻关键词提取改为本地 TF-IDF（native_extract_news_keywords）
+-- 沿用外部接口原有的 limit=500、top_k=5，保留任务上已有的执行控制参数
+-- ============================================================
+UPDATE scheduled_tasks
+SET params = (params - 'arg' - 'url')
+                 || '{"cmd": "native_extract_news_keywords", "limit": 500, "top_k": 5}'::jsonb
+WHERE name = '提取新闻关键字到news_keywords'
+  AND params ->> 'cmd' = 'extract_keywords_to_news_keywords';
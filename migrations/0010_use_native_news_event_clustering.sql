@@ -0,0 +1,8 @@
+-- ============================================================
+-- 新闻事件提取改为本地聚类（native_extract_news_event）
+-- 使用命令默认的阈值和最少条数，保留任务上已有的执行控制参数
+-- ============================================================
+UPDATE scheduled_tasks
+SET params = (params - 'arg' - 'url') || '{"cmd": "native_extract_news_event"}'::jsonb
+WHERE name = '提取新闻事件'
+  AND params ->> 'cmd' = 'extract_news_event';
@@ -0,0 +1,9 @@
+-- ============================================================
+-- 跨天新闻事件合并改为本地实现（native_merge_news_events）
+-- 沿用外部接口原有的 days=2，保留任务上已有的执行控制参数（如 misfire_policy）
+-- ============================================================
+UPDATE scheduled_tasks
+SET params = (params - 'arg' - 'url')
+                 || '{"cmd": "native_merge_news_events", "days": 2}'::jsonb
+WHERE name = '合并新闻事件'
+  AND params ->> 'cmd' = 'merge_cross_day_news_events';
@@ -0,0 +1,31 @@
+-- ============================================================
+-- Table: health_check_result（健康检测历史）
+-- health_check 命令每检测一个地址记录一行，用于统计可用率和延迟
+-- ============================================================
+CREATE TABLE IF NOT EXISTS health_check_result
+(
+    id          BIGSERIAL PRIMARY KEY,
+    url         TEXT        NOT NULL,
+    success     BOOLEAN     NOT NULL,
+    status_code INTEGER,
+    latency_ms  INTEGER     NOT NULL DEFAULT 0,
+    error       TEXT,
+    checked_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE health_check_result IS '健康检测历史，每次检测一个地址一条记录';
+COMMENT ON COLUMN health_check_result.success IS '是否通过全部断言（状态码、延迟、JSONPath、正文正则）';
+COMMENT ON COLUMN health_check_result.status_code IS 'HTTP 状态码，请求未得到响应时为 NULL';
+COMMENT ON COLUMN health_check_result.latency_ms IS '从发出请求到读完响应正文的耗时（毫秒）';
+COMMENT ON COLUMN health_check_result.error IS '未通过的断言或请求失败原因';
+
+CREATE INDEX IF NOT EXISTS idx_health_check_result_url_checked
+    ON health_check_result (url, checked_at DESC);
+
+CREATE TRIGGER health_check_result_updated_at_trg
+    BEFORE UPDATE
+    ON health_check_result
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,29 @@
+-- ============================================================
+-- Table: http_fetch_cache（抓取缓存）
+-- 按源地址记录上次抓取的 ETag / Last-Modified / 内容哈希，
+-- 源数据未变化时跳过写入，避免反复改写 news_info 并触发重新抽取
+-- ============================================================
+CREATE TABLE IF NOT EXISTS http_fetch_cache
+(
+    url           TEXT PRIMARY KEY,
+    etag          TEXT,
+    last_modified TEXT,
+    content_hash  VARCHAR(32),
+    stored_date   DATE,
+    fetched_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE http_fetch_cache IS '按源地址记录的抓取缓存，用于条件请求和内容去重';
+COMMENT ON COLUMN http_fetch_cache.etag IS '上次响应的 ETag，下次请求作为 If-None-Match';
+COMMENT ON COLUMN http_fetch_cache.last_modified IS '上次响应的 Last-Modified，下次请求作为 If-Modified-Since';
+COMMENT ON COLUMN http_fetch_cache.content_hash IS '上次写入的条目内容哈希';
+COMMENT ON COLUMN http_fetch_cache.stored_date IS '上次写入 news_info 的日期，跨天后不再跳过，保证每天都有当天的批次';
+COMMENT ON COLUMN http_fetch_cache.fetched_at IS '最近一次抓取时间';
+
+CREATE TRIGGER http_fetch_cache_updated_at_trg
+    BEFORE UPDATE
+    ON http_fetch_cache
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
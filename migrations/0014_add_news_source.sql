@@ -0,0 +1,48 @@
+-- ============================================================
+-- Table: news_source（新闻源登记表）
+-- id 与 news_info.news_from 一致（聚合源为上游 sourceId，RSS / HTML 为 `rss:` / `html:` 前缀的来源标识），
+-- 抓取命令据此跳过停用的源、改用登记的名称；未登记的源在首次抓取时自动登记
+-- ============================================================
+CREATE TABLE IF NOT EXISTS news_source
+(
+    id           VARCHAR(50) PRIMARY KEY,
+    display_name VARCHAR(50) NOT NULL,
+    category     VARCHAR(50),
+    enabled      BOOLEAN     NOT NULL DEFAULT true,
+    fetch_kind   VARCHAR(20) NOT NULL DEFAULT 'aggregator',
+    url          TEXT,
+    priority     INTEGER     NOT NULL DEFAULT 0,
+    language     VARCHAR(16) NOT NULL DEFAULT 'zh',
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT chk_news_source_fetch_kind CHECK (fetch_kind IN ('aggregator', 'rss', 'html'))
+);
+
+COMMENT ON TABLE news_source IS '新闻源登记表，控制抓取启停、展示名称和分类';
+COMMENT ON COLUMN news_source.id IS '来源标识，与 news_info.news_from 一致';
+COMMENT ON COLUMN news_source.display_name IS '展示名称，抓取时覆盖上游返回的名称写入 news_info.name';
+COMMENT ON COLUMN news_source.category IS '新闻分类，为空时 SSE 推送沿用来源名称';
+COMMENT ON COLUMN news_source.enabled IS '是否抓取该源，停用后抓取命令直接跳过';
+COMMENT ON COLUMN news_source.fetch_kind IS '抓取方式：aggregator（fetch_all_news）/ rss（fetch_rss）/ html（scrape_html）';
+COMMENT ON COLUMN news_source.url IS '抓取地址';
+COMMENT ON COLUMN news_source.priority IS '排序优先级，越大越靠前';
+COMMENT ON COLUMN news_source.language IS '内容语言';
+
+CREATE TRIGGER news_source_updated_at_trg
+    BEFORE UPDATE
+    ON news_source
+    FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- 按已有批次登记历史来源，名称取最近一天的批次
+INSERT INTO news_source (id, display_name, fetch_kind)
+SELECT DISTINCT ON (news_from) news_from,
+                               COALESCE(NULLIF(TRIM(name), ''), news_from),
+                               CASE
+                                   WHEN news_from LIKE 'rss:%' THEN 'rss'
+                                   WHEN news_from LIKE 'html:%' THEN 'html'
+                                   ELSE 'aggregator'
+                                   END
+FROM news_info
+ORDER BY news_from, news_date DESC
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,27 @@
+-- ============================================================
+-- RBAC：路由权限（RequirePermission 按 scope 校验）
+-- task:read    查询定时任务、可用命令与执行记录
+-- task:write   创建 / 修改 / 启停 / 删除 / 立即执行定时任务、预览触发时间、同步任务数据源
+-- news:curate  管理新闻源登记
+-- ============================================================
+INSERT INTO permissions (name, description)
+VALUES ('task:read', '查询定时任务及执行记录'),
+       ('task:write', '创建、修改、执行定时任务'),
+       ('news:curate', '管理新闻源登记')
+ON CONFLICT (name) DO NOTHING;
+
+-- admin 拥有全部权限
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r
+         JOIN permissions p ON p.name IN ('task:read', 'task:write', 'news:curate')
+WHERE r.name = 'admin'
+ON CONFLICT DO NOTHING;
+
+-- editor 可以查看定时任务、管理新闻源
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r
+         JOIN permissions p ON p.name IN ('task:read', 'news:curate')
+WHERE r.name = 'editor'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,36 @@
+-- ============================================================
+-- refresh_tokens 会话信息
+-- 同一次登录轮换出的 refresh_token 共享 family_id，作为一个会话展示和吊销；
+-- 已吊销的 token 被再次使用时吊销整个 family（疑似泄露后重放）
+-- ============================================================
+CREATE SEQUENCE IF NOT EXISTS refresh_token_family_seq;
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN IF NOT EXISTS family_id  BIGINT,
+    ADD COLUMN IF NOT EXISTS user_agent TEXT,
+    ADD COLUMN IF NOT EXISTS ip_address VARCHAR(64);
+
+-- 历史 token 无法追溯轮换关系，各自作为一个会话
+UPDATE refresh_tokens
+SET family_id = nextval('refresh_token_family_seq')
+WHERE family_id IS NULL;
+
+ALTER TABLE refresh_tokens
+    ALTER COLUMN family_id SET DEFAULT nextval('refresh_token_family_seq'),
+    ALTER COLUMN family_id SET NOT NULL;
+
+COMMENT ON COLUMN refresh_tokens.family_id IS '会话 ID，登录时生成，刷新轮换出的新 token 沿用';
+COMMENT ON COLUMN refresh_tokens.user_agent IS '签发该 token 时（登录或刷新）客户端的 User-Agent';
+COMMENT ON COLUMN refresh_tokens.ip_address IS '签发该 token 时（登录或刷新）客户端的 IP';
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens (family_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_user_family ON refresh_tokens (user_id, family_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_token ON refresh_tokens (token);
+
+-- 每天清理过期的 refresh_token
+INSERT INTO scheduled_tasks (name, cron, params, is_enabled, retry_times, last_status, timezone)
+VALUES ('清理过期refresh_token',
+        '0 30 4 * * * *',
+        '{"cmd": "cleanup_expired_refresh_tokens", "retention_days": 1}',
+        true, 0, '0', 'Asia/Shanghai')
+ON CONFLICT (name) DO NOTHING;
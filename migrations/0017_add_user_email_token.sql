@@ -0,0 +1,29 @@
+-- ============================================================
+-- 邮箱验证与找回密码
+-- 链接中的 token 只在邮件里出现，库中仅保存其 SHA-256 摘要；
+-- token 一次性使用，过期或使用后失效
+-- ============================================================
+ALTER TABLE user_info
+    ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN user_info.email_verified_at IS '邮箱验证时间，NULL 表示未验证';
+
+CREATE TABLE IF NOT EXISTS user_email_token
+(
+    id         BIGSERIAL PRIMARY KEY,
+    user_id    BIGINT       NOT NULL REFERENCES user_info (id) ON DELETE CASCADE,
+    purpose    VARCHAR(32)  NOT NULL CHECK (purpose IN ('verify_email', 'reset_password')),
+    token_hash CHAR(64)     NOT NULL UNIQUE,
+    email      VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMPTZ  NOT NULL,
+    used_at    TIMESTAMPTZ,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT now()
+);
+
+COMMENT ON TABLE user_email_token IS '邮件中发送的一次性 token（邮箱验证 / 重置密码）';
+COMMENT ON COLUMN user_email_token.purpose IS '用途：verify_email、reset_password';
+COMMENT ON COLUMN user_email_token.token_hash IS 'token 的 SHA-256 十六进制摘要';
+COMMENT ON COLUMN user_email_token.email IS '发送时的邮箱，用户改邮箱后旧 token 不再生效';
+COMMENT ON COLUMN user_email_token.used_at IS '使用时间，NULL 表示未使用';
+
+CREATE INDEX IF NOT EXISTS idx_user_email_token_user_purpose ON user_email_token (user_id, purpose, created_at DESC);
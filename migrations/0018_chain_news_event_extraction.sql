@@ -0,0 +1,10 @@
+-- ============================================================
+-- 提取新闻事件依赖关键词提取：原 cron 与关键词提取相同，只晚 10 秒，
+-- 改为关键词提取成功后立即执行，不再依靠错开的秒数；执行频率不变
+-- 抽取 news_item 与跨天合并保持各自的 cron（及合并任务的 misfire_policy），不参与串联
+-- 已手动配置过 depends_on 的任务保持不变
+-- ============================================================
+UPDATE scheduled_tasks
+SET params = params || '{"depends_on": ["提取新闻关键字到news_keywords"]}'::jsonb
+WHERE name = '提取新闻事件'
+  AND NOT params ? 'depends_on';
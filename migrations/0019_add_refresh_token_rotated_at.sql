@@ -0,0 +1,7 @@
+-- ============================================================
+-- refresh_tokens 记录轮换时间
+-- 只有因刷新被轮换的 token 再次使用才视为重放并吊销整个会话；
+-- 退出登录、吊销会话、重置密码等主动吊销的 token 再次使用只返回 401
+-- ============================================================
+ALTER TABLE refresh_tokens
+    ADD COLUMN IF NOT EXISTS rotated_at TIMESTAMPTZ;
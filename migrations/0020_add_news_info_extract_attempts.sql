@@ -0,0 +1,8 @@
+-- ============================================================
+-- news_info 抽取失败次数
+-- 失败达到上限的批次不再参与抽取，避免一直占用批次名额；数据更新后重新计数
+-- ============================================================
+ALTER TABLE news_info
+    ADD COLUMN IF NOT EXISTS extract_attempts INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN news_info.extract_attempts IS '抽取到 news_item 连续失败的次数，达到上限后不再重试';
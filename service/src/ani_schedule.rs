@@ -0,0 +1,266 @@
+use chrono::{DateTime, Utc};
+use common::api::ApiResponse;
+use common::po::{AniItem, ItemResult, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use common::utils::extract_number;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::process_news_info::HTTP_CLIENT;
+
+const BILIBILI: &str = "bilibili";
+const IQIYI: &str = "iqiyi";
+
+/// 抓取 B 站番剧时间表中今日已更新的剧集，存入 ani_info 表
+/// api_url 格式: https://api.bilibili.com/pgc/web/timeline?types=1&before=1&after=1
+pub async fn fetch_bilibili_ani(
+    api_url: String,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let json_value = get_json(&api_url, "https://www.bilibili.com/").await?;
+    let items = parse_bilibili_timeline(&json_value)?;
+    Ok(ApiResponse::ok(build_ani_result(items)))
+}
+
+/// 抓取爱奇艺动漫更新表，存入 ani_info 表
+/// api_url 为爱奇艺动漫频道更新表接口地址
+pub async fn fetch_iqiyi_ani(api_url: String) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let json_value = get_json(&api_url, "https://www.iqiyi.com/").await?;
+    let items = parse_iqiyi_schedule(&json_value, Utc::now())?;
+    Ok(ApiResponse::ok(build_ani_result(items)))
+}
+
+async fn get_json(api_url: &str, referer: &str) -> Result<Value, String> {
+    let response = HTTP_CLIENT
+        .get(api_url)
+        .header("Referer", referer)
+        .header(
+            "User-Agent",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36",
+        )
+        .send()
+        .await
+        .map_err(|e| format!("获取番剧更新表失败 {api_url}: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "HTTP错误: {} - 状态码: {}",
+            api_url,
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析番剧更新表响应失败 {api_url}: {e}"))
+}
+
+fn build_ani_result(items: Vec<AniItem>) -> ItemResult {
+    let weekday = get_today_weekday().name_cn.to_string();
+    let all_items: HashSet<TaskItem> = items.into_iter().map(TaskItem::Ani).collect();
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    result
+}
+
+/// 解析 B 站番剧时间表
+///
+/// 只取 is_today 为 1 的日期下已发布（published = 1）且未停更（delay = 0）的剧集，
+/// update_time 取剧集的发布时间
+pub fn parse_bilibili_timeline(json_value: &Value) -> Result<Vec<AniItem>, String> {
+    if json_value["code"].as_i64() != Some(0) {
+        return Err(format!(
+            "B 站接口返回错误: code={} message={}",
+            json_value["code"],
+            json_value["message"].as_str().unwrap_or_default()
+        ));
+    }
+    let days = json_value["result"]
+        .as_array()
+        .ok_or("B 站接口响应缺少 result 数组")?;
+    let Some(today) = days.iter().find(|day| day["is_today"].as_i64() == Some(1)) else {
+        return Ok(Vec::new());
+    };
+
+    let items = today["episodes"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter(|ep| ep["published"].as_i64() == Some(1) && ep["delay"].as_i64() != Some(1))
+        .filter_map(|ep| {
+            let title = non_empty(&ep["title"])?;
+            let pub_index = non_empty(&ep["pub_index"])?;
+            let season_id = ep["season_id"].as_i64()?;
+            let update_time = DateTime::from_timestamp(ep["pub_ts"].as_i64()?, 0)?;
+            Some(AniItem {
+                title,
+                update_info: format!("更新至{pub_index}"),
+                update_count: pub_index,
+                image_url: non_empty(&ep["cover"])
+                    .map(|url| absolute_url(&url))
+                    .unwrap_or_default(),
+                detail_url: format!("https://www.bilibili.com/bangumi/play/ss{season_id}"),
+                update_time,
+                platform: BILIBILI.to_string(),
+            })
+        })
+        .collect();
+    Ok(items)
+}
+
+/// 解析爱奇艺动漫更新表
+///
+/// 更新表按卡片层层嵌套，递归收集同时带 display_name 和 page_url 的节点；
+/// 接口不返回单集更新时间，update_time 取抓取时间
+pub fn parse_iqiyi_schedule(
+    json_value: &Value,
+    fetched_at: DateTime<Utc>,
+) -> Result<Vec<AniItem>, String> {
+    if json_value["code"].as_i64().is_some_and(|code| code != 0) {
+        return Err(format!(
+            "爱奇艺接口返回错误: code={} msg={}",
+            json_value["code"],
+            json_value["msg"].as_str().unwrap_or_default()
+        ));
+    }
+    let mut nodes = Vec::new();
+    collect_iqiyi_videos(&json_value["data"], &mut nodes);
+
+    let items = nodes
+        .into_iter()
+        .filter_map(|video| {
+            let title = non_empty(&video["display_name"])?;
+            let detail_url = absolute_url(&non_empty(&video["page_url"])?);
+            let update_info = non_empty(&video["dq_updatestatus"]).unwrap_or_default();
+            let update_count = extract_number(&update_info)
+                .map(|n| format!("第{n}集"))
+                .unwrap_or_else(|| update_info.clone());
+            let image_url = non_empty(&video["image_cover"])
+                .or_else(|| non_empty(&video["image_url"]))
+                .map(|url| absolute_url(&url))
+                .unwrap_or_default();
+            Some(AniItem {
+                title,
+                update_count,
+                update_info,
+                image_url,
+                detail_url,
+                update_time: fetched_at,
+                platform: IQIYI.to_string(),
+            })
+        })
+        .collect();
+    Ok(items)
+}
+
+fn collect_iqiyi_videos<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) if map.contains_key("display_name") && map.contains_key("page_url") => {
+            out.push(value)
+        }
+        Value::Object(map) => map.values().for_each(|v| collect_iqiyi_videos(v, out)),
+        Value::Array(values) => values.iter().for_each(|v| collect_iqiyi_videos(v, out)),
+        _ => {}
+    }
+}
+
+fn non_empty(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// 协议相对地址补全为 https
+fn absolute_url(url: &str) -> String {
+    match url.strip_prefix("//") {
+        Some(rest) => format!("https://{rest}"),
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bilibili_timeline, parse_iqiyi_schedule};
+    use chrono::{TimeZone, Utc};
+    use serde_json::{Value, json};
+
+    fn fixture(content: &str) -> Value {
+        serde_json::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn parse_bilibili_timeline_keeps_published_episodes_of_today() {
+        let json_value = fixture(include_str!("../tests/fixtures/bilibili_timeline.json"));
+        let items = parse_bilibili_timeline(&json_value).unwrap();
+
+        assert_eq!(items.len(), 1);
+        let ani = &items[0];
+        assert_eq!(ani.title, "示例番剧");
+        assert_eq!(ani.update_count, "第10话");
+        assert_eq!(ani.update_info, "更新至第10话");
+        assert_eq!(
+            ani.image_url,
+            "http://i0.hdslb.com/bfs/bangumi/image/a1b2c3.png"
+        );
+        assert_eq!(
+            ani.detail_url,
+            "https://www.bilibili.com/bangumi/play/ss48100"
+        );
+        assert_eq!(
+            ani.update_time,
+            Utc.with_ymd_and_hms(2025, 9, 16, 15, 0, 0).unwrap()
+        );
+        assert_eq!(ani.platform, "bilibili");
+    }
+
+    #[test]
+    fn parse_bilibili_timeline_reports_api_errors() {
+        assert!(parse_bilibili_timeline(&json!({"code": -404, "message": "啥都木有"})).is_err());
+        assert!(
+            parse_bilibili_timeline(&json!({"code": 0, "result": []}))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn parse_iqiyi_schedule_collects_nested_videos() {
+        let json_value = fixture(include_str!("../tests/fixtures/iqiyi_schedule.json"));
+        let fetched_at = Utc.with_ymd_and_hms(2025, 9, 16, 4, 0, 0).unwrap();
+        let items = parse_iqiyi_schedule(&json_value, fetched_at).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "示例国漫");
+        assert_eq!(items[0].update_count, "第24集");
+        assert_eq!(items[0].update_info, "更新至24集");
+        assert_eq!(
+            items[0].detail_url,
+            "https://www.iqiyi.com/v_19rr1abcd.html"
+        );
+        assert_eq!(
+            items[0].image_url,
+            "https://pic1.iqiyipic.com/image/20250916/ab/cd/a_1001.jpg"
+        );
+        assert_eq!(items[0].update_time, fetched_at);
+        assert_eq!(items[0].platform, "iqiyi");
+
+        assert_eq!(items[1].update_count, "第12集");
+        assert_eq!(
+            items[1].detail_url,
+            "https://www.iqiyi.com/a_19rr1efgh.html"
+        );
+        assert_eq!(
+            items[1].image_url,
+            "https://pic2.iqiyipic.com/image/20250101/ef/gh/a_1002.jpg"
+        );
+    }
+
+    #[test]
+    fn parse_iqiyi_schedule_reports_api_errors() {
+        let fetched_at = Utc::now();
+        assert!(parse_iqiyi_schedule(&json!({"code": 1, "msg": "参数错误"}), fetched_at).is_err());
+    }
+}
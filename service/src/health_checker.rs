@@ -1,63 +1,415 @@
-use anyhow::{Context, Result};
 use common::api::ApiResponse;
+use common::dto::NewHealthCheckResultDTO;
 use common::po::{HealthItem, ItemResult, TaskItem};
 use common::utils::date_utils::get_today_weekday;
+use infra::insert_health_check_result;
+use regex::Regex;
+use serde_json::{Value, json};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
 use tokio::task::JoinSet;
 use tracing::{error, warn};
 
 use crate::process_news_info::HTTP_CLIENT;
+use crate::timer_task_command::task_db_pool;
+
+/// 健康检测的断言，对应任务 params 中的同名字段，对 arg 中的每个地址生效
+#[derive(Debug)]
+pub struct HealthCheckSpec {
+    /// 期望的状态码，缺省时要求 2xx
+    expect_status: Option<u16>,
+    /// 允许的最大耗时（毫秒）
+    max_latency_ms: Option<u64>,
+    /// 是否要求响应为 JSON，默认 true
+    expect_json: bool,
+    /// 断言取值的 JSONPath，如 `$.data.status`
+    json_path: Option<String>,
+    /// JSONPath 取值应等于的内容
+    json_equals: Option<String>,
+    /// JSONPath 取值应包含的内容
+    json_contains: Option<String>,
+    /// 响应正文应匹配的正则
+    body_regex: Option<Regex>,
+}
+
+impl HealthCheckSpec {
+    pub fn from_params(params: &Value) -> Result<Self, String> {
+        let text = |name: &str| {
+            params
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let expect_status = match params.get("expect_status").and_then(Value::as_i64) {
+            Some(code @ 100..=599) => Some(code as u16),
+            Some(code) => return Err(format!("params.expect_status 不是合法的状态码: {code}")),
+            None => None,
+        };
+        let body_regex = match text("body_regex") {
+            Some(pattern) => Some(
+                Regex::new(&pattern)
+                    .map_err(|e| format!("params.body_regex 不是合法的正则 '{pattern}': {e}"))?,
+            ),
+            None => None,
+        };
+        let spec = Self {
+            expect_status,
+            max_latency_ms: params
+                .get("max_latency_ms")
+                .and_then(Value::as_i64)
+                .map(|ms| ms.max(1) as u64),
+            expect_json: params
+                .get("expect_json")
+                .and_then(Value::as_bool)
+                .unwrap_or(true),
+            json_path: text("json_path"),
+            json_equals: text("json_equals"),
+            json_contains: text("json_contains"),
+            body_regex,
+        };
+        if spec.json_path.is_none() && (spec.json_equals.is_some() || spec.json_contains.is_some())
+        {
+            return Err("params.json_equals / json_contains 需要同时配置 json_path".to_string());
+        }
+        if let Some(path) = &spec.json_path {
+            json_path_lookup(&Value::Null, path)?;
+        }
+        Ok(spec)
+    }
+
+    /// 按断言检查响应，返回解析出的 JSON 和未通过的断言
+    pub fn evaluate(
+        &self,
+        status: u16,
+        latency_ms: u64,
+        body: &str,
+    ) -> (Option<Value>, Vec<String>) {
+        let mut failures = Vec::new();
+        match self.expect_status {
+            Some(expected) if status != expected => {
+                failures.push(format!("状态码 {status}，期望 {expected}"))
+            }
+            None if !(200..300).contains(&status) => {
+                failures.push(format!("状态码 {status} 不是 2xx"))
+            }
+            _ => {}
+        }
+        if let Some(max) = self.max_latency_ms
+            && latency_ms > max
+        {
+            failures.push(format!("耗时 {latency_ms}ms 超过 {max}ms"));
+        }
+
+        let json_value = match serde_json::from_str::<Value>(body) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                if self.expect_json || self.json_path.is_some() {
+                    failures.push(format!("响应不是合法的 JSON: {e}"));
+                }
+                None
+            }
+        };
+        if let (Some(path), Some(value)) = (&self.json_path, &json_value) {
+            match json_path_lookup(value, path) {
+                Ok(Some(target)) => {
+                    if let Some(expected) = &self.json_equals
+                        && !json_equals(target, expected)
+                    {
+                        failures.push(format!("JSONPath {path} 的值 {target} 不等于 {expected}"));
+                    }
+                    if let Some(expected) = &self.json_contains
+                        && !json_contains(target, expected)
+                    {
+                        failures.push(format!("JSONPath {path} 的值 {target} 不包含 {expected}"));
+                    }
+                }
+                Ok(None) => failures.push(format!("JSONPath {path} 不存在")),
+                Err(e) => failures.push(e),
+            }
+        }
+        if let Some(re) = &self.body_regex
+            && !re.is_match(body)
+        {
+            failures.push(format!("响应正文不匹配正则 {re}"));
+        }
+        (json_value, failures)
+    }
+}
+
+/// 单个地址的检测结果
+#[derive(Debug)]
+struct CheckOutcome {
+    url: String,
+    status_code: Option<u16>,
+    latency_ms: u64,
+    body: Option<Value>,
+    failures: Vec<String>,
+}
 
 /// 获取健康检测的数据</br>
-/// args: 待检测的url，如果是多个url,使用英文","隔开
-pub async fn health_check(args: String) -> Result<ApiResponse<ItemResult>, String> {
-    let sources: Vec<&str> = args.split(',').map(|s| s.trim()).collect();
-    let mut result: ItemResult = HashMap::new();
-    let weekday = get_today_weekday().name_cn.to_string();
+/// args: 待检测的url，如果是多个url,使用英文","隔开；params 中可声明断言，见 [`HealthCheckSpec`]
+///
+/// 每个地址的结果写入 health_check_result；任一地址未通过断言时返回错误，本次执行记为失败
+/// 命令声明为不重试，一次执行只为每个地址写入一条记录
+pub async fn health_check(args: String, params: Value) -> Result<ApiResponse<ItemResult>, String> {
+    let spec = Arc::new(HealthCheckSpec::from_params(&params)?);
+    let pool = task_db_pool()?;
+    let sources: Vec<String> = args
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
 
     let mut join_set = JoinSet::new();
 
     // 添加所有任务到 JoinSet
-    for arg in sources {
-        let arg = arg.to_string();
-        join_set.spawn(async move { health_check_single(&arg).await });
+    for url in &sources {
+        let url = url.clone();
+        let spec = Arc::clone(&spec);
+        join_set.spawn(async move { health_check_single(url, &spec).await });
     }
 
-    // 收集所有结果
-    let mut all_items: HashSet<TaskItem> = HashSet::new();
+    // 收集所有结果，异常退出的检测任务按失败处理
+    let mut outcomes = Vec::new();
     while let Some(task_result) = join_set.join_next().await {
         match task_result {
-            Ok(Ok(item)) => {
-                all_items.insert(TaskItem::Health(item));
-            }
-            Ok(Err(e)) => warn!("获取检测的url失败 {}", e),
+            Ok(outcome) => outcomes.push(outcome),
             Err(e) => error!("任务执行失败 {}", e),
         }
     }
-    result.insert(weekday, all_items);
+    for url in &sources {
+        if !outcomes.iter().any(|o| &o.url == url) {
+            outcomes.push(CheckOutcome {
+                url: url.clone(),
+                status_code: None,
+                latency_ms: 0,
+                body: None,
+                failures: vec!["检测任务异常退出".to_string()],
+            });
+        }
+    }
+    outcomes.sort_by(|a, b| a.url.cmp(&b.url));
+
+    for outcome in &outcomes {
+        let record = NewHealthCheckResultDTO {
+            url: outcome.url.clone(),
+            success: outcome.failures.is_empty(),
+            status_code: outcome.status_code.map(i32::from),
+            latency_ms: outcome.latency_ms.min(i32::MAX as u64) as i32,
+            error: (!outcome.failures.is_empty()).then(|| outcome.failures.join("；")),
+        };
+        if let Err(e) = insert_health_check_result(&record, pool).await {
+            warn!("保存 {} 的健康检测结果失败: {e}", outcome.url);
+        }
+    }
+
+    let failed: Vec<String> = outcomes
+        .iter()
+        .filter(|o| !o.failures.is_empty())
+        .map(|o| format!("{}: {}", o.url, o.failures.join("；")))
+        .collect();
+    if !failed.is_empty() {
+        return Err(format!(
+            "健康检测未通过 {}/{}: {}",
+            failed.len(),
+            outcomes.len(),
+            failed.join("\n")
+        ));
+    }
+
+    let all_items: HashSet<TaskItem> = outcomes
+        .into_iter()
+        .map(|o| {
+            TaskItem::Health(HealthItem {
+                result: json!({
+                    "statusCode": o.status_code,
+                    "latencyMs": o.latency_ms,
+                    "body": o.body,
+                }),
+                url: o.url,
+            })
+        })
+        .collect();
+    let mut result: ItemResult = HashMap::new();
+    result.insert(get_today_weekday().name_cn.to_string(), all_items);
     Ok(ApiResponse::ok(result))
 }
 
-async fn health_check_single(url: &str) -> Result<HealthItem> {
-    let response = HTTP_CLIENT
-        .get(url)
-        .header("Referer", url)
-        .send()
-        .await
-        .with_context(|| format!("请求待检测url {url} 失败"))?;
-
-    // 检查HTTP状态码
-    if !response.status().is_success() {
-        anyhow::bail!("请求 {url} 错误- 状态码: {}", response.status());
-    }
-    // 将响应解析成json
-    let json_value: serde_json::Value = response
-        .json()
-        .await
-        .with_context(|| format!("解析 {url} 的JSON响应失败"))?;
-
-    Ok(HealthItem {
-        url: url.to_string(),
-        result: json_value,
-    })
+async fn health_check_single(url: String, spec: &HealthCheckSpec) -> CheckOutcome {
+    let started = Instant::now();
+    let response = HTTP_CLIENT.get(&url).header("Referer", &url).send().await;
+    let (status_code, body) = match response {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            match response.text().await {
+                Ok(body) => (status, body),
+                Err(e) => {
+                    return CheckOutcome {
+                        latency_ms: started.elapsed().as_millis() as u64,
+                        status_code: Some(status),
+                        body: None,
+                        failures: vec![format!("读取响应失败: {e}")],
+                        url,
+                    };
+                }
+            }
+        }
+        Err(e) => {
+            return CheckOutcome {
+                latency_ms: started.elapsed().as_millis() as u64,
+                status_code: None,
+                body: None,
+                failures: vec![format!("请求失败: {e}")],
+                url,
+            };
+        }
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let (body, failures) = spec.evaluate(status_code, latency_ms, &body);
+    CheckOutcome {
+        url,
+        status_code: Some(status_code),
+        latency_ms,
+        body,
+        failures,
+    }
+}
+
+/// 按 JSONPath 取值，支持 `$.a.b`、`$.a[0]`、`$['a']` 这类逐级下标写法
+///
+/// 路径格式错误时返回 Err，路径合法但取不到值时返回 Ok(None)
+pub fn json_path_lookup<'a>(value: &'a Value, path: &str) -> Result<Option<&'a Value>, String> {
+    let invalid = || format!("不是合法的 JSONPath: '{path}'");
+    let mut rest = path.trim();
+    rest = rest.strip_prefix('$').unwrap_or(rest);
+    let mut current = Some(value);
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let segment = after[..end].trim();
+            rest = &after[end + 1..];
+            let quoted = segment
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .or_else(|| segment.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+            current = match quoted {
+                Some(key) => current.and_then(|v| v.get(key)),
+                None => {
+                    let index: usize = segment.parse().map_err(|_| invalid())?;
+                    current.and_then(|v| v.get(index))
+                }
+            };
+        } else {
+            let after = rest.strip_prefix('.').unwrap_or(rest);
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            rest = &after[end..];
+            current = current.and_then(|v| v.get(key));
+        }
+    }
+    Ok(current)
+}
+
+/// 字符串按原文比较，其余类型把 expected 解析为 JSON 后比较（如 `true`、`200`）
+fn json_equals(target: &Value, expected: &str) -> bool {
+    target.as_str() == Some(expected)
+        || serde_json::from_str::<Value>(expected).is_ok_and(|value| &value == target)
+}
+
+/// 字符串包含子串、数组包含等于 expected 的元素、对象包含名为 expected 的字段
+fn json_contains(target: &Value, expected: &str) -> bool {
+    match target {
+        Value::String(s) => s.contains(expected),
+        Value::Array(values) => values.iter().any(|v| json_equals(v, expected)),
+        Value::Object(map) => map.contains_key(expected),
+        other => other.to_string().contains(expected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HealthCheckSpec, json_path_lookup};
+    use serde_json::json;
+
+    #[test]
+    fn json_path_lookup_walks_keys_and_indexes() {
+        let value = json!({"data": {"items": [{"name": "a"}, {"name": "b"}], "a.b": 1}});
+
+        assert_eq!(
+            json_path_lookup(&value, "$.data.items[1].name").unwrap(),
+            Some(&json!("b"))
+        );
+        assert_eq!(
+            json_path_lookup(&value, "$['data']['a.b']").unwrap(),
+            Some(&json!(1))
+        );
+        assert_eq!(json_path_lookup(&value, "$").unwrap(), Some(&value));
+        assert_eq!(json_path_lookup(&value, "$.data.missing").unwrap(), None);
+        assert!(json_path_lookup(&value, "$.data.items[x]").is_err());
+        assert!(json_path_lookup(&value, "$..name").is_err());
+    }
+
+    #[test]
+    fn evaluate_reports_every_failed_assertion() {
+        let spec = HealthCheckSpec::from_params(&json!({
+            "expect_status": 200,
+            "max_latency_ms": 500,
+            "json_path": "$.status",
+            "json_equals": "UP",
+            "body_regex": "\"version\":\\s*\"\\d+"
+        }))
+        .unwrap();
+
+        let (body, failures) = spec.evaluate(200, 120, r#"{"status": "UP", "version": "2.1"}"#);
+        assert_eq!(body.unwrap()["status"], "UP");
+        assert!(failures.is_empty());
+
+        let (_, failures) = spec.evaluate(503, 900, r#"{"status": "DOWN"}"#);
+        assert_eq!(failures.len(), 4);
+        assert_eq!(failures[0], "状态码 503，期望 200");
+        assert_eq!(failures[1], "耗时 900ms 超过 500ms");
+        assert_eq!(failures[2], r#"JSONPath $.status 的值 "DOWN" 不等于 UP"#);
+
+        let contains = HealthCheckSpec::from_params(&json!({
+            "json_path": "$.services",
+            "json_contains": "db"
+        }))
+        .unwrap();
+        assert!(
+            contains
+                .evaluate(200, 1, r#"{"services": ["db", "cache"]}"#)
+                .1
+                .is_empty()
+        );
+        assert_eq!(contains.evaluate(200, 1, r#"{"services": []}"#).1.len(), 1);
+        assert_eq!(contains.evaluate(200, 1, "<html>").1.len(), 1);
+
+        let code =
+            HealthCheckSpec::from_params(&json!({"json_path": "$.code", "json_equals": "0"}))
+                .unwrap();
+        assert!(code.evaluate(200, 1, r#"{"code": 0}"#).1.is_empty());
+        assert_eq!(code.evaluate(200, 1, r#"{"code": "1"}"#).1.len(), 1);
+    }
+
+    #[test]
+    fn spec_defaults_to_2xx_json_and_rejects_invalid_params() {
+        let spec = HealthCheckSpec::from_params(&json!({"cmd": "health_check"})).unwrap();
+        assert!(spec.evaluate(204, 1, "{}").1.is_empty());
+        assert_eq!(spec.evaluate(200, 1, "ok").1.len(), 1);
+        assert_eq!(spec.evaluate(301, 1, "{}").1, vec!["状态码 301 不是 2xx"]);
+
+        let plain = HealthCheckSpec::from_params(&json!({"expect_json": false})).unwrap();
+        assert!(plain.evaluate(200, 1, "ok").1.is_empty());
+
+        assert!(HealthCheckSpec::from_params(&json!({"json_equals": "UP"})).is_err());
+        assert!(HealthCheckSpec::from_params(&json!({"body_regex": "("})).is_err());
+        assert!(HealthCheckSpec::from_params(&json!({"expect_status": 42})).is_err());
+        assert!(HealthCheckSpec::from_params(&json!({"json_path": "$.a[b"})).is_err());
+    }
 }
@@ -0,0 +1,313 @@
+use common::api::ApiResponse;
+use common::po::{ItemResult, NewsInfo, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::news_source::{FETCH_KIND_HTML, apply_news_source, is_disabled, lookup_news_source};
+use crate::process_news_info::HTTP_CLIENT;
+use crate::rss_feed::{fnv1a_64, html_to_text, parse_rfc3339, resolve_link, source_id};
+use crate::timer_task_command::task_db_pool;
+
+/// 按 CSS 选择器抓取的页面配置，对应任务 params 中的同名字段
+#[derive(Debug)]
+pub struct ScrapeConfig {
+    /// 列表条目选择器
+    item: Selector,
+    /// 条目内标题选择器
+    title: Selector,
+    /// 条目内链接选择器，缺省取第一个带 href 的 a 标签
+    link: Option<Selector>,
+    /// 条目内发布时间选择器
+    date: Option<Selector>,
+    /// 条目内图片选择器
+    image: Option<Selector>,
+    /// 来源名称，缺省取页面 title
+    source_name: Option<String>,
+}
+
+impl ScrapeConfig {
+    pub fn from_params(params: &Value) -> Result<Self, String> {
+        let required = |name: &str| {
+            optional_selector(params, name)?.ok_or_else(|| format!("params.{name} 为必填项"))
+        };
+        Ok(Self {
+            item: required("item_selector")?,
+            title: required("title_selector")?,
+            link: optional_selector(params, "link_selector")?,
+            date: optional_selector(params, "date_selector")?,
+            image: optional_selector(params, "image_selector")?,
+            source_name: params
+                .get("source_name")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string),
+        })
+    }
+}
+
+fn optional_selector(params: &Value, name: &str) -> Result<Option<Selector>, String> {
+    match params.get(name).and_then(Value::as_str).map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(s) => Selector::parse(s)
+            .map(Some)
+            .map_err(|e| format!("params.{name} 不是合法的 CSS 选择器 '{s}': {e}")),
+    }
+}
+
+/// 抓取 HTML 列表页，按任务 params 中的 CSS 选择器提取条目，整理成一条 NewsInfo 存入 news_info 表
+/// page_url 格式: https://example.com/news
+///
+/// news_source 中停用的源直接跳过，已登记的源改用登记名称，未登记的源自动登记
+pub async fn scrape_html(
+    page_url: String,
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let config = ScrapeConfig::from_params(&params)?;
+    let pool = task_db_pool()?;
+    let weekday = get_today_weekday().name_cn.to_string();
+    let source = lookup_news_source(&source_id(FETCH_KIND_HTML, &page_url), pool).await;
+    if is_disabled(source.as_ref()) {
+        info!("页面 {page_url} 已停用，跳过抓取");
+        return Ok(ApiResponse::ok(HashMap::from([(weekday, HashSet::new())])));
+    }
+    let response = HTTP_CLIENT
+        .get(&page_url)
+        .send()
+        .await
+        .map_err(|e| format!("获取页面失败 {page_url}: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "HTTP错误: {} - 状态码: {}",
+            page_url,
+            response.status()
+        ));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("读取页面响应失败 {page_url}: {e}"))?;
+    let news = scrape_page(&body, &page_url, &config)?;
+    let news = apply_news_source(news, source.as_ref(), FETCH_KIND_HTML, &page_url, pool).await;
+
+    let mut all_items = HashSet::new();
+    all_items.insert(TaskItem::News(news));
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+/// 解析 HTML 页面
+///
+/// 条目结构与聚合源保持一致：`{ id, title, url, pubDate?, extra? }`，
+/// extra.date 为页面上的原始时间文本，extra.image 为补全后的图片地址。
+/// 列表选择器一条都匹配不到时视为页面结构变化，返回错误
+pub fn scrape_page(html: &str, page_url: &str, config: &ScrapeConfig) -> Result<NewsInfo, String> {
+    let document = Html::parse_document(html);
+
+    let mut matched = 0;
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    for element in document.select(&config.item) {
+        matched += 1;
+        if let Some(item) = scrape_item(element, page_url, config)
+            && seen.insert(item["id"].as_str().unwrap_or_default().to_string())
+        {
+            items.push(item);
+        }
+    }
+    if matched == 0 {
+        return Err(format!("列表选择器未匹配到任何条目: {page_url}"));
+    }
+
+    let id = source_id(FETCH_KIND_HTML, page_url);
+    let name = config.source_name.clone().or_else(|| {
+        let title = Selector::parse("title").ok()?;
+        document
+            .select(&title)
+            .next()
+            .map(|el| element_text(el))
+            .filter(|t| !t.is_empty())
+    });
+    Ok(NewsInfo {
+        name: name.unwrap_or_else(|| id.clone()),
+        id,
+        items,
+    })
+}
+
+/// 提取单个条目；标题为空的条目直接丢弃，条目 id 取链接，没有链接时取标题哈希
+fn scrape_item(element: ElementRef, page_url: &str, config: &ScrapeConfig) -> Option<Value> {
+    let title = element
+        .select(&config.title)
+        .next()
+        .map(element_text)
+        .filter(|t| !t.is_empty())?;
+
+    let link = match &config.link {
+        Some(selector) => element
+            .select(selector)
+            .find_map(|el| el.value().attr("href")),
+        None => element.value().attr("href").or_else(|| {
+            let anchor = Selector::parse("a[href]").ok()?;
+            element
+                .select(&anchor)
+                .next()
+                .and_then(|el| el.value().attr("href"))
+        }),
+    }
+    .map(str::trim)
+    .filter(|href| !href.is_empty())
+    .map(|href| resolve_link(page_url, href));
+
+    let date = config.date.as_ref().and_then(|selector| {
+        let el = element.select(selector).next()?;
+        el.value()
+            .attr("datetime")
+            .or_else(|| el.value().attr("content"))
+            .map(|s| s.trim().to_string())
+            .or_else(|| Some(element_text(el)))
+            .filter(|s| !s.is_empty())
+    });
+
+    let image = config.image.as_ref().and_then(|selector| {
+        let el = element.select(selector).next()?;
+        ["data-src", "data-original", "src"]
+            .iter()
+            .filter_map(|attr| el.value().attr(attr))
+            .map(str::trim)
+            .find(|src| !src.is_empty() && !src.starts_with("data:"))
+            .map(|src| resolve_link(page_url, src))
+    });
+
+    let id = link
+        .clone()
+        .unwrap_or_else(|| format!("{:016x}", fnv1a_64(&title)));
+
+    let mut entry = Map::new();
+    entry.insert("id".to_string(), json!(id));
+    entry.insert("title".to_string(), json!(title));
+    entry.insert("url".to_string(), json!(link.unwrap_or_default()));
+    if let Some(published) = date.as_deref().and_then(parse_rfc3339) {
+        entry.insert("pubDate".to_string(), json!(published.timestamp_millis()));
+    }
+    let mut extra = Map::new();
+    if let Some(date) = date {
+        extra.insert("date".to_string(), json!(date));
+    }
+    if let Some(image) = image {
+        extra.insert("image".to_string(), json!(image));
+    }
+    if !extra.is_empty() {
+        entry.insert("extra".to_string(), Value::Object(extra));
+    }
+    Some(Value::Object(entry))
+}
+
+/// 元素内的纯文本，压缩空白
+fn element_text(element: ElementRef) -> String {
+    html_to_text(&element.text().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScrapeConfig, scrape_page};
+    use serde_json::json;
+
+    const NEWS_LIST_FIXTURE: &str = include_str!("../tests/fixtures/news_list.html");
+    const PAGE_URL: &str = "https://anime.example.com/news/latest";
+
+    fn config(params: serde_json::Value) -> ScrapeConfig {
+        ScrapeConfig::from_params(&params).unwrap()
+    }
+
+    #[test]
+    fn scrape_page_extracts_configured_fields() {
+        let config = config(json!({
+            "cmd": "scrape_html",
+            "arg": PAGE_URL,
+            "item_selector": "ul.news-list > li.news-item",
+            "title_selector": ".news-title",
+            "link_selector": "a.news-link",
+            "date_selector": "time",
+            "image_selector": "img.cover"
+        }));
+        let news = scrape_page(NEWS_LIST_FIXTURE, PAGE_URL, &config).unwrap();
+
+        assert_eq!(news.name, "示例动漫资讯 - 最新新闻");
+        assert!(news.id.starts_with("html:anime.example.com:"));
+        // 无标题的广告条目被丢弃，链接重复的条目只保留第一条
+        assert_eq!(news.items.len(), 3);
+        assert_eq!(
+            news.items[0],
+            json!({
+                "id": "https://anime.example.com/news/1001.html",
+                "title": "《示例番》第二季 确认十月播出",
+                "url": "https://anime.example.com/news/1001.html",
+                "pubDate": 1757980800000i64,
+                "extra": {
+                    "date": "2025-09-16T08:00:00+08:00",
+                    "image": "https://anime.example.com/images/1001.jpg"
+                }
+            })
+        );
+        assert_eq!(
+            news.items[1],
+            json!({
+                "id": "https://cdn.example.org/news/1002",
+                "title": "剧场版票房突破十亿",
+                "url": "https://cdn.example.org/news/1002",
+                "extra": {
+                    "date": "2025-09-15",
+                    "image": "https://img.example.org/1002.webp"
+                }
+            })
+        );
+        assert_eq!(news.items[2]["title"], "没有链接的快讯");
+        assert_eq!(news.items[2]["url"], "");
+        assert_eq!(news.items[2]["id"].as_str().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn scrape_page_uses_defaults_for_optional_fields() {
+        let config = config(json!({
+            "item_selector": "li.news-item",
+            "title_selector": "h3",
+            "source_name": "示例动漫"
+        }));
+        let news = scrape_page(NEWS_LIST_FIXTURE, PAGE_URL, &config).unwrap();
+
+        assert_eq!(news.name, "示例动漫");
+        assert_eq!(
+            news.items[0],
+            json!({
+                "id": "https://anime.example.com/news/1001.html",
+                "title": "《示例番》第二季 确认十月播出",
+                "url": "https://anime.example.com/news/1001.html"
+            })
+        );
+    }
+
+    #[test]
+    fn scrape_page_fails_when_item_selector_matches_nothing() {
+        let config = config(json!({
+            "item_selector": "div.article",
+            "title_selector": "h3"
+        }));
+        assert!(scrape_page(NEWS_LIST_FIXTURE, PAGE_URL, &config).is_err());
+    }
+
+    #[test]
+    fn scrape_config_rejects_missing_or_invalid_selectors() {
+        assert!(ScrapeConfig::from_params(&json!({"item_selector": "li"})).is_err());
+        assert!(
+            ScrapeConfig::from_params(&json!({"item_selector": "li", "title_selector": "h3["}))
+                .is_err()
+        );
+    }
+}
@@ -1,4 +1,13 @@
+pub mod ani_schedule;
 pub mod health_checker;
+pub mod html_scraper;
+pub mod news_event_cluster;
+pub mod news_event_merge;
+pub mod news_extractor;
+pub mod news_keywords;
+pub mod news_source;
 pub mod process_news_info;
+pub mod refresh_token_cleanup;
 pub mod register_service;
+pub mod rss_feed;
 pub mod timer_task_command;
@@ -0,0 +1,380 @@
+use chrono::{Local, NaiveDate};
+use common::api::ApiResponse;
+use common::po::{
+    HealthItem, ItemResult, NewNewsEvent, NewsEventPipelineRun, NewsItemText, TaskItem,
+};
+use common::utils::date_utils::get_today_weekday;
+use infra::{
+    delete_stale_news_events, insert_news_event_pipeline_run, list_news_item_texts_by_date,
+    replace_news_event_items, reset_news_item_clusters, upsert_news_event,
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Instant;
+use tracing::{info, warn};
+
+use crate::news_keywords::{tfidf_weights, tokenize};
+use crate::timer_task_command::task_db_pool;
+
+/// news_item.cluster_method 取值
+pub const CLUSTER_METHOD: &str = "tfidf_cosine";
+/// 默认的最低余弦相似度
+const DEFAULT_THRESHOLD: f64 = 0.2;
+/// 默认生成事件所需的最少新闻条数
+const DEFAULT_MIN_SIZE: i64 = 2;
+const MAX_MIN_SIZE: i64 = 50;
+
+/// 一次聚类得到的新闻事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewsCluster {
+    /// 事件内最小的新闻条目 id，同一天内唯一且在事件增长时保持不变
+    pub cluster_id: i64,
+    /// 按 id 升序的成员新闻条目
+    pub news_ids: Vec<i64>,
+    pub title: String,
+    pub summary: Option<String>,
+    pub score: f32,
+}
+
+/// 本地聚类某一天的 news_item 生成 news_event，不再依赖外部分析服务
+///
+/// params.event_date 为聚类日期（默认当天），params.threshold 为最低余弦相似度（默认 0.2），
+/// params.min_size 为生成事件所需的最少新闻条数（默认 2）
+pub async fn native_extract_news_event(
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let event_date = match params.get("event_date").and_then(Value::as_str) {
+        Some(s) => NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("params.event_date 不是 YYYY-MM-DD 格式的日期: '{s}'"))?,
+        None => Local::now().date_naive(),
+    };
+    let threshold = params
+        .get("threshold")
+        .and_then(Value::as_f64)
+        .unwrap_or(DEFAULT_THRESHOLD)
+        .clamp(0.05, 1.0);
+    let min_size = params
+        .get("min_size")
+        .and_then(Value::as_i64)
+        .unwrap_or(DEFAULT_MIN_SIZE)
+        .clamp(2, MAX_MIN_SIZE) as usize;
+    let pool = task_db_pool()?;
+    let summary = cluster_news_events(pool, event_date, threshold, min_size).await?;
+
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::ExtractNewsEvent(HealthItem {
+        url: "native_extract_news_event".to_string(),
+        result: summary,
+    });
+    let mut all_items = HashSet::new();
+    all_items.insert(item);
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+/// 聚类 event_date 当天的新闻条目并写入 news_event / news_event_item，回写 news_item.cluster_id
+///
+/// 当天的聚类结果在同一事务中整体替换：不再成立的自动事件被删除，人工确认或参与过合并的事件保留。
+/// 加载、聚类、写入三个步骤分别记录到 news_event_pipeline_run；
+/// 返回 `{ event_date, items, events, deleted }` 统计
+pub async fn cluster_news_events(
+    pool: &PgPool,
+    event_date: NaiveDate,
+    threshold: f64,
+    min_size: usize,
+) -> Result<Value, String> {
+    let started = Instant::now();
+    let rows = list_news_item_texts_by_date(event_date, pool)
+        .await
+        .map_err(|e| e.to_string());
+    let rows = record_step(pool, "load_news_items", event_date, started, rows, Vec::len).await?;
+
+    let started = Instant::now();
+    if rows.is_empty() {
+        let skipped = NewsEventPipelineRun {
+            step_name: "cluster_news_items".to_string(),
+            event_date: Some(event_date),
+            affected_rows: 0,
+            cost_ms: 0,
+            status: 3,
+            message: Some("当天没有新闻条目".to_string()),
+        };
+        if let Err(e) = insert_news_event_pipeline_run(&skipped, pool).await {
+            warn!("记录事件流水线步骤失败: {e}");
+        }
+        return Ok(json!({ "event_date": event_date, "items": 0, "events": 0, "deleted": 0 }));
+    }
+    let clusters = cluster_news_items(&rows, threshold, min_size);
+    let clusters = record_step(
+        pool,
+        "cluster_news_items",
+        event_date,
+        started,
+        Ok(clusters),
+        Vec::len,
+    )
+    .await?;
+
+    let started = Instant::now();
+    let saved = save_clusters(pool, event_date, &clusters)
+        .await
+        .map_err(|e| format!("写入新闻事件失败: {e}"));
+    let deleted = record_step(pool, "save_news_events", event_date, started, saved, |_| {
+        clusters.len()
+    })
+    .await?;
+
+    info!(
+        "本地聚类 {event_date} 新闻事件完成: 条目 {}，事件 {}，删除 {deleted}",
+        rows.len(),
+        clusters.len()
+    );
+    Ok(json!({
+        "event_date": event_date,
+        "items": rows.len(),
+        "events": clusters.len(),
+        "deleted": deleted,
+    }))
+}
+
+/// 记录单个步骤的耗时和结果；记录本身失败只打日志，不影响聚类结果
+pub(crate) async fn record_step<T>(
+    pool: &PgPool,
+    step_name: &str,
+    event_date: NaiveDate,
+    started: Instant,
+    outcome: Result<T, String>,
+    affected_rows: impl Fn(&T) -> usize,
+) -> Result<T, String> {
+    let (affected, status, message) = match &outcome {
+        Ok(value) => (affected_rows(value), 0, None),
+        Err(e) => (0, 1, Some(e.clone())),
+    };
+    let run = NewsEventPipelineRun {
+        step_name: step_name.to_string(),
+        event_date: Some(event_date),
+        affected_rows: affected as i32,
+        cost_ms: started.elapsed().as_millis() as i32,
+        status,
+        message,
+    };
+    if let Err(e) = insert_news_event_pipeline_run(&run, pool).await {
+        warn!("记录事件流水线步骤 {step_name} 失败: {e}");
+    }
+    outcome
+}
+
+/// 在同一事务中写入当天的聚类结果，返回删除的过期事件数
+async fn save_clusters(
+    pool: &PgPool,
+    event_date: NaiveDate,
+    clusters: &[NewsCluster],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    reset_news_item_clusters(event_date, &mut tx).await?;
+    for cluster in clusters {
+        let event = NewNewsEvent {
+            event_date,
+            cluster_id: cluster.cluster_id,
+            title: cluster.title.clone(),
+            summary: cluster.summary.clone(),
+            news_count: cluster.news_ids.len() as i32,
+            score: cluster.score,
+        };
+        let event_id = upsert_news_event(&event, &mut tx).await?;
+        replace_news_event_items(
+            event_id,
+            cluster.cluster_id,
+            CLUSTER_METHOD,
+            &cluster.news_ids,
+            &mut tx,
+        )
+        .await?;
+    }
+    let cluster_ids: Vec<i64> = clusters.iter().map(|c| c.cluster_id).collect();
+    let deleted = delete_stale_news_events(event_date, &cluster_ids, &mut tx).await?;
+    tx.commit().await?;
+    Ok(deleted)
+}
+
+/// 按标题 TF-IDF 向量的余弦相似度对新闻条目做单链接聚类
+///
+/// 相似度不低于 threshold 的两条新闻归入同一事件，成员数不足 min_size 的簇丢弃。
+/// 事件标题取与其他成员相似度之和最高的条目（相同时取 id 较小者），摘要取该条目的内容；
+/// 热度 score = 条数 × (1 + ln(来源数))。结果按 score 从高到低、cluster_id 从小到大排列，
+/// 与输入顺序无关
+pub fn cluster_news_items(
+    items: &[NewsItemText],
+    threshold: f64,
+    min_size: usize,
+) -> Vec<NewsCluster> {
+    let mut items: Vec<&NewsItemText> = items.iter().collect();
+    items.sort_by_key(|item| item.id);
+    let docs: Vec<Vec<String>> = items.iter().map(|item| tokenize(&item.title)).collect();
+    let vectors: Vec<HashMap<String, f64>> = tfidf_weights(&docs)
+        .into_iter()
+        .map(|weights| normalize(weights.into_iter().collect()))
+        .collect();
+
+    let n = items.len();
+    let mut similarity = vec![vec![0.0; n]; n];
+    let mut parent: Vec<usize> = (0..n).collect();
+    for i in 0..n {
+        for j in i + 1..n {
+            let sim = cosine(&vectors[i], &vectors[j]);
+            similarity[i][j] = sim;
+            similarity[j][i] = sim;
+            if sim >= threshold {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+
+    // 以根节点分组，BTreeMap 保证遍历顺序稳定
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..n {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+
+    let mut clusters: Vec<NewsCluster> = groups
+        .into_values()
+        .filter(|members| members.len() >= min_size)
+        .map(|members| {
+            let representative = *members
+                .iter()
+                .max_by(|&&a, &&b| {
+                    let sum = |i: usize| members.iter().map(|&j| similarity[i][j]).sum::<f64>();
+                    sum(a).total_cmp(&sum(b)).then_with(|| b.cmp(&a))
+                })
+                .expect("簇内至少有一条新闻");
+            let sources: HashSet<&str> = members
+                .iter()
+                .map(|&i| items[i].source.as_deref().unwrap_or_default())
+                .collect();
+            let news_count = members.len() as f64;
+            NewsCluster {
+                cluster_id: items[members[0]].id,
+                news_ids: members.iter().map(|&i| items[i].id).collect(),
+                title: items[representative].title.clone(),
+                summary: items[representative].content.clone(),
+                score: (news_count * (1.0 + (sources.len() as f64).ln())) as f32,
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.cluster_id.cmp(&b.cluster_id))
+    });
+    clusters
+}
+
+pub(crate) fn normalize(mut vector: HashMap<String, f64>) -> HashMap<String, f64> {
+    let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        vector.values_mut().for_each(|w| *w /= norm);
+    }
+    vector
+}
+
+/// 两个已归一化向量的余弦相似度
+pub(crate) fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(token, w)| large.get(token).map(|v| w * v))
+        .sum()
+}
+
+fn find(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut node = i;
+    while parent[node] != root {
+        let next = parent[node];
+        parent[node] = root;
+        node = next;
+    }
+    root
+}
+
+/// 合并两个集合，总以较小的下标为根
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra.max(rb)] = ra.min(rb);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cluster_news_items;
+    use common::po::NewsItemText;
+    use serde_json::Value;
+
+    fn fixture() -> Vec<NewsItemText> {
+        let items: Vec<Value> =
+            serde_json::from_str(include_str!("../tests/fixtures/news_event_items.json")).unwrap();
+        items
+            .into_iter()
+            .map(|item| NewsItemText {
+                id: item["id"].as_i64().unwrap(),
+                title: item["title"].as_str().unwrap().to_string(),
+                content: item["content"].as_str().map(str::to_string),
+                source: item["source"].as_str().map(str::to_string),
+                extracted: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cluster_news_items_groups_fixture_by_title_similarity() {
+        let clusters = cluster_news_items(&fixture(), 0.2, 2);
+
+        let members: Vec<(i64, Vec<i64>)> = clusters
+            .iter()
+            .map(|c| (c.cluster_id, c.news_ids.clone()))
+            .collect();
+        assert_eq!(
+            members,
+            vec![
+                (101, vec![101, 104, 108]),
+                (103, vec![103, 106]),
+                (105, vec![105, 109]),
+            ]
+        );
+        assert_eq!(clusters[0].title, "iPhone 17发布会：苹果新机售价公布");
+        assert_eq!(
+            clusters[0].summary.as_deref(),
+            Some("苹果秋季发布会今日凌晨举行")
+        );
+        // 3 条新闻来自 3 个来源：3 × (1 + ln3)
+        assert!((clusters[0].score - 6.295837).abs() < 1e-4);
+        // 2 条新闻来自 2 个来源：2 × (1 + ln2)
+        assert!((clusters[1].score - 3.386294).abs() < 1e-4);
+        assert_eq!(clusters[1].score, clusters[2].score);
+    }
+
+    #[test]
+    fn cluster_news_items_is_deterministic_and_respects_limits() {
+        let items = fixture();
+        let mut reversed = items.clone();
+        reversed.reverse();
+        assert_eq!(
+            cluster_news_items(&items, 0.2, 2),
+            cluster_news_items(&reversed, 0.2, 2)
+        );
+
+        let large_only = cluster_news_items(&items, 0.2, 3);
+        assert_eq!(large_only.len(), 1);
+        assert_eq!(large_only[0].cluster_id, 101);
+
+        assert!(cluster_news_items(&items, 1.0, 2).is_empty());
+        assert!(cluster_news_items(&[], 0.2, 2).is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{Days, Local, NaiveDate};
+use common::api::ApiResponse;
+use common::po::{HealthItem, ItemResult, NewsEventMergeCandidate, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use infra::{
+    list_news_event_keywords, list_news_event_member_urls, list_news_events_between,
+    merge_news_event,
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use tracing::info;
+
+use crate::news_event_cluster::{cosine, normalize, record_step};
+use crate::news_keywords::tokenize;
+use crate::timer_task_command::task_db_pool;
+
+/// 默认向前比较的天数
+const DEFAULT_DAYS: i64 = 2;
+const MAX_DAYS: i64 = 30;
+/// 默认的最低合并相似度
+const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// 参与合并比较的事件及其成员链接、关键词向量
+#[derive(Debug, Clone)]
+pub struct MergeEvent {
+    pub event: NewsEventMergeCandidate,
+    pub urls: HashSet<String>,
+    /// 成员新闻关键词权重之和；没有关键词时取事件标题分词
+    pub keywords: HashMap<String, f64>,
+}
+
+/// 一次合并：子事件挂到父事件（所在时间线的根事件）下
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMerge {
+    pub child_id: i64,
+    pub parent_id: i64,
+    pub score: f64,
+}
+
+/// 本地合并跨天新闻事件，不再依赖外部分析服务
+///
+/// params.event_date 为窗口最后一天（默认当天），params.days 为向前比较的天数（默认 2），
+/// params.threshold 为最低合并相似度（默认 0.5）
+pub async fn native_merge_news_events(
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let end_date = match params.get("event_date").and_then(Value::as_str) {
+        Some(s) => NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("params.event_date 不是 YYYY-MM-DD 格式的日期: '{s}'"))?,
+        None => Local::now().date_naive(),
+    };
+    let days = params
+        .get("days")
+        .and_then(Value::as_i64)
+        .unwrap_or(DEFAULT_DAYS)
+        .clamp(1, MAX_DAYS);
+    let threshold = params
+        .get("threshold")
+        .and_then(Value::as_f64)
+        .unwrap_or(DEFAULT_THRESHOLD)
+        .clamp(0.05, 1.0);
+    let pool = task_db_pool()?;
+    let summary = merge_news_events(pool, end_date, days as u64, threshold).await?;
+
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::MergeNewsItem(HealthItem {
+        url: "native_merge_news_events".to_string(),
+        result: summary,
+    });
+    let mut all_items = HashSet::new();
+    all_items.insert(item);
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+/// 合并 [end_date - days, end_date] 窗口内的新闻事件，结果在同一事务中写入
+///
+/// 运行结果记录到 news_event_pipeline_run（步骤 merge_cross_day_events）；
+/// 返回 `{ start_date, end_date, events, merged }` 统计
+pub async fn merge_news_events(
+    pool: &PgPool,
+    end_date: NaiveDate,
+    days: u64,
+    threshold: f64,
+) -> Result<Value, String> {
+    let started = Instant::now();
+    let start_date = end_date - Days::new(days);
+    let outcome = load_and_merge(pool, start_date, end_date, threshold).await;
+    let (events, merges) = record_step(
+        pool,
+        "merge_cross_day_events",
+        end_date,
+        started,
+        outcome,
+        |(_, merges)| merges.len(),
+    )
+    .await?;
+
+    info!(
+        "本地合并 {start_date} ~ {end_date} 新闻事件完成: 事件 {events}，合并 {}",
+        merges.len()
+    );
+    Ok(json!({
+        "start_date": start_date,
+        "end_date": end_date,
+        "events": events,
+        "merged": merges.len(),
+    }))
+}
+
+async fn load_and_merge(
+    pool: &PgPool,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    threshold: f64,
+) -> Result<(usize, Vec<EventMerge>), String> {
+    let candidates = list_news_events_between(start_date, end_date, pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let ids: Vec<i64> = candidates.iter().map(|e| e.id).collect();
+    let mut urls: HashMap<i64, HashSet<String>> = HashMap::new();
+    for (event_id, url) in list_news_event_member_urls(&ids, pool)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        urls.entry(event_id).or_default().insert(url);
+    }
+    let mut keywords: HashMap<i64, HashMap<String, f64>> = HashMap::new();
+    for (event_id, keyword, weight) in list_news_event_keywords(&ids, pool)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        keywords
+            .entry(event_id)
+            .or_default()
+            .insert(keyword, weight);
+    }
+
+    let events: Vec<MergeEvent> = candidates
+        .into_iter()
+        .map(|event| MergeEvent {
+            urls: urls.remove(&event.id).unwrap_or_default(),
+            keywords: keywords.remove(&event.id).unwrap_or_default(),
+            event,
+        })
+        .collect();
+    let merges = plan_event_merges(&events, threshold);
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    for merge in &merges {
+        merge_news_event(merge.child_id, merge.parent_id, &mut tx)
+            .await
+            .map_err(|e| format!("合并新闻事件失败: {e}"))?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok((events.len(), merges))
+}
+
+/// 计算跨天合并方案
+///
+/// 按日期、id 依次处理尚未合并的自动事件（status = 0），在更早日期的事件中找相似度最高的一个，
+/// 相似度 = max(成员链接重合度, 关键词余弦相似度)，重合度 = 共同链接数 / 较小一方的链接数。
+/// 相似度不低于 threshold 时挂到该事件所在时间线的根事件下，相同时取日期更早、id 更小的事件。
+/// 同一天的事件之间不合并，人工确认和已归档的事件只作为父事件
+pub fn plan_event_merges(events: &[MergeEvent], threshold: f64) -> Vec<EventMerge> {
+    let mut events: Vec<&MergeEvent> = events.iter().collect();
+    events.sort_by_key(|e| (e.event.event_date, e.event.id));
+    let vectors: Vec<HashMap<String, f64>> = events.iter().map(|e| keyword_vector(e)).collect();
+    let mut roots: HashMap<i64, i64> = events
+        .iter()
+        .map(|e| (e.event.id, e.event.parent_event_id.unwrap_or(e.event.id)))
+        .collect();
+
+    let mut merges = Vec::new();
+    for (i, child) in events.iter().enumerate() {
+        if child.event.status != 0 || child.event.parent_event_id.is_some() {
+            continue;
+        }
+        let best = events[..i]
+            .iter()
+            .enumerate()
+            .filter(|(_, parent)| parent.event.event_date < child.event.event_date)
+            .map(|(j, parent)| {
+                let score =
+                    url_overlap(&child.urls, &parent.urls).max(cosine(&vectors[i], &vectors[j]));
+                (score, parent.event.id)
+            })
+            // 按日期、id 升序遍历，只有严格更高的相似度才替换
+            .fold(None, |best: Option<(f64, i64)>, candidate| match best {
+                Some(b) if b.0 >= candidate.0 => Some(b),
+                _ => Some(candidate),
+            });
+        if let Some((score, parent_id)) = best
+            && score >= threshold
+        {
+            let root = roots[&parent_id];
+            roots.insert(child.event.id, root);
+            merges.push(EventMerge {
+                child_id: child.event.id,
+                parent_id: root,
+                score,
+            });
+        }
+    }
+    merges
+}
+
+fn keyword_vector(event: &MergeEvent) -> HashMap<String, f64> {
+    if !event.keywords.is_empty() {
+        return normalize(event.keywords.clone());
+    }
+    let title = event.event.title.as_deref().unwrap_or_default();
+    normalize(
+        tokenize(title)
+            .into_iter()
+            .map(|token| (token, 1.0))
+            .collect(),
+    )
+}
+
+fn url_overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / smaller as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MergeEvent, plan_event_merges};
+    use common::po::NewsEventMergeCandidate;
+    use serde_json::Value;
+
+    fn fixture() -> Vec<MergeEvent> {
+        let events: Vec<Value> =
+            serde_json::from_str(include_str!("../tests/fixtures/news_event_merge.json")).unwrap();
+        events
+            .into_iter()
+            .map(|e| MergeEvent {
+                event: NewsEventMergeCandidate {
+                    id: e["id"].as_i64().unwrap(),
+                    event_date: e["event_date"].as_str().unwrap().parse().unwrap(),
+                    title: e["title"].as_str().map(str::to_string),
+                    status: e["status"].as_i64().unwrap() as i16,
+                    parent_event_id: e["parent_event_id"].as_i64(),
+                },
+                urls: e["urls"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|u| u.as_str().unwrap().to_string())
+                    .collect(),
+                keywords: e["keywords"]
+                    .as_object()
+                    .unwrap()
+                    .iter()
+                    .map(|(k, w)| (k.clone(), w.as_f64().unwrap()))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_event_merges_links_fixture_into_timelines() {
+        let merges = plan_event_merges(&fixture(), 0.5);
+
+        let pairs: Vec<(i64, i64)> = merges.iter().map(|m| (m.child_id, m.parent_id)).collect();
+        // 11 与 1 有一半链接重合；21 与 11 关键词最相似，挂到时间线的根事件 1；
+        // 22 没有关键词，按标题分词与 2 比较；13 为人工确认事件，3 与 1 同一天，都不合并
+        assert_eq!(pairs, vec![(11, 1), (21, 1), (22, 2)]);
+        assert_eq!(merges[0].score, 0.5);
+        assert!(merges.iter().all(|m| m.score >= 0.5));
+
+        assert!(plan_event_merges(&fixture(), 0.8).is_empty());
+    }
+
+    #[test]
+    fn plan_event_merges_follows_existing_parents_outside_window() {
+        // 窗口从第二天开始，11 已在之前的运行中合并到 1
+        let events: Vec<MergeEvent> = fixture()
+            .into_iter()
+            .filter(|e| e.event.id > 10)
+            .map(|mut e| {
+                if e.event.id == 11 {
+                    e.event.status = 3;
+                    e.event.parent_event_id = Some(1);
+                }
+                e
+            })
+            .collect();
+
+        let merges = plan_event_merges(&events, 0.5);
+
+        assert_eq!(merges.len(), 2);
+        assert_eq!((merges[0].child_id, merges[0].parent_id), (21, 1));
+        assert_eq!((merges[1].child_id, merges[1].parent_id), (22, 13));
+    }
+}
@@ -0,0 +1,243 @@
+use common::api::{ApiResponse, NewsInfo2Item};
+use common::po::{HealthItem, ItemResult, NewsInfoBatch, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use infra::{
+    list_unextracted_news_info, record_news_info_extract_error, upsert_news_info_extracted_state,
+    upsert_news_item,
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use tracing::{info, warn};
+
+use crate::timer_task_command::task_db_pool;
+
+/// 单次执行默认处理的批次数
+const DEFAULT_BATCH_SIZE: i64 = 50;
+/// 单次执行最多处理的批次数
+const MAX_BATCH_SIZE: i64 = 500;
+/// 单个批次最多抽取失败的次数，达到后不再重试
+const MAX_EXTRACT_ATTEMPTS: i32 = 3;
+
+/// 本地抽取 news_info 到 news_item，不再依赖外部分析服务
+///
+/// params.batch_size 为单次处理的 news_info 批次数，默认 50
+pub async fn native_extract_news_item(
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let batch_size = params
+        .get("batch_size")
+        .and_then(Value::as_i64)
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+        .clamp(1, MAX_BATCH_SIZE);
+    let pool = task_db_pool()?;
+    let summary = extract_news_info_batches(pool, batch_size).await?;
+
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::ExtractNewsItem(HealthItem {
+        url: "native_extract_news_item".to_string(),
+        result: summary,
+    });
+    let mut all_items = HashSet::new();
+    all_items.insert(item);
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+/// 逐批抽取未处理的 news_info，每个批次在独立事务中写入 news_item 并标记已抽取
+///
+/// 单个批次失败时回滚该批次并把原因写入 news_info.error，不影响其余批次；
+/// 连续失败 [`MAX_EXTRACT_ATTEMPTS`] 次的批次不再抽取，数据更新后重新计数。
+/// 返回 `{ batches, items, failed }` 统计
+pub async fn extract_news_info_batches(pool: &PgPool, batch_size: i64) -> Result<Value, String> {
+    let batches = list_unextracted_news_info(batch_size, MAX_EXTRACT_ATTEMPTS, pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut extracted_batches = 0;
+    let mut extracted_items = 0;
+    let mut failed = 0;
+    for batch in &batches {
+        let outcome = match normalize_news_items(batch) {
+            Ok(items) => save_batch(pool, batch.id, &items)
+                .await
+                .map(|_| items.len())
+                .map_err(|e| format!("写入 news_item 失败: {e}")),
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(count) => {
+                extracted_batches += 1;
+                extracted_items += count;
+            }
+            Err(e) => {
+                failed += 1;
+                warn!(
+                    "抽取 news_info [{}] {} 失败: {e}",
+                    batch.id, batch.news_from
+                );
+                let attempts = record_news_info_extract_error(batch.id, &e, pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if attempts >= MAX_EXTRACT_ATTEMPTS {
+                    warn!(
+                        "news_info [{}] 已连续抽取失败 {attempts} 次，不再重试",
+                        batch.id
+                    );
+                }
+            }
+        }
+    }
+
+    info!(
+        "本地抽取 news_info 完成: 批次 {extracted_batches}，条目 {extracted_items}，失败 {failed}"
+    );
+    Ok(json!({
+        "batches": extracted_batches,
+        "items": extracted_items,
+        "failed": failed,
+    }))
+}
+
+async fn save_batch(
+    pool: &PgPool,
+    news_info_id: i64,
+    items: &[NewsInfo2Item],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for item in items {
+        upsert_news_item(item, &mut tx).await?;
+    }
+    upsert_news_info_extracted_state(news_info_id, &mut tx).await?;
+    tx.commit().await
+}
+
+/// 把批次原始 data 中的条目整理成 news_item
+///
+/// data 为 `{ "items": [...] }`（也兼容直接存数组）；条目缺少标题或链接时跳过，
+/// 条目 id 缺失时以链接代替，同一批次内 id 重复的条目只保留第一条。
+/// content 取 extra.hover，没有时取 description / content 字段
+pub fn normalize_news_items(batch: &NewsInfoBatch) -> Result<Vec<NewsInfo2Item>, String> {
+    let data = batch.data.as_ref().ok_or("data 为空")?;
+    let raw_items = data
+        .get("items")
+        .unwrap_or(data)
+        .as_array()
+        .ok_or("data 中缺少 items 数组")?;
+    let name = batch
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&batch.news_from)
+        .to_string();
+
+    let mut seen = HashSet::new();
+    let items = raw_items
+        .iter()
+        .filter_map(|raw| {
+            let title = text(raw.get("title")?)?;
+            let url = raw
+                .get("url")
+                .and_then(text)
+                .or_else(|| raw.get("mobileUrl").and_then(text))?;
+            let news_item_id = match raw.get("id") {
+                Some(Value::Number(n)) => n.to_string(),
+                Some(value) => text(value).unwrap_or_else(|| url.clone()),
+                None => url.clone(),
+            };
+            let extra = raw.get("extra").cloned().unwrap_or(Value::Null);
+            let content = extra
+                .get("hover")
+                .and_then(text)
+                .or_else(|| raw.get("description").and_then(text))
+                .or_else(|| raw.get("content").and_then(text));
+            Some(NewsInfo2Item {
+                id: batch.id,
+                news_from: batch.news_from.clone(),
+                name: name.clone(),
+                news_date: batch.news_date,
+                news_item_id,
+                title,
+                url,
+                content,
+                extra,
+            })
+        })
+        .filter(|item| seen.insert(item.news_item_id.clone()))
+        .collect();
+    Ok(items)
+}
+
+/// 字符串字段去掉多余空白，空串视为缺失
+fn text(value: &Value) -> Option<String> {
+    let s = value
+        .as_str()?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_news_items;
+    use chrono::NaiveDate;
+    use common::po::NewsInfoBatch;
+    use serde_json::{Value, json};
+
+    fn batch(data: Option<Value>) -> NewsInfoBatch {
+        NewsInfoBatch {
+            id: 7,
+            news_from: "weibo".to_string(),
+            name: Some("微博热搜".to_string()),
+            news_date: NaiveDate::from_ymd_opt(2025, 9, 16).unwrap(),
+            data,
+        }
+    }
+
+    #[test]
+    fn normalize_news_items_maps_raw_items() {
+        let items = normalize_news_items(&batch(Some(json!({
+            "items": [
+                {
+                    "id": "abc",
+                    "title": "  第一条\n新闻 ",
+                    "url": "https://a.com/1",
+                    "extra": { "hover": "摘要", "info": "热度 1万" }
+                },
+                { "id": 42, "title": "数字 id", "mobileUrl": "https://m.a.com/42" },
+                { "title": "没有 id", "url": "https://a.com/3", "description": "描述" },
+                { "id": "abc", "title": "重复 id", "url": "https://a.com/dup" },
+                { "id": "no-url", "title": "没有链接" },
+                { "id": "no-title", "url": "https://a.com/5" }
+            ]
+        }))))
+        .unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].id, 7);
+        assert_eq!(items[0].name, "微博热搜");
+        assert_eq!(items[0].news_item_id, "abc");
+        assert_eq!(items[0].title, "第一条 新闻");
+        assert_eq!(items[0].content.as_deref(), Some("摘要"));
+        assert_eq!(items[0].extra["info"], "热度 1万");
+        assert_eq!(items[1].news_item_id, "42");
+        assert_eq!(items[1].url, "https://m.a.com/42");
+        assert_eq!(items[2].news_item_id, "https://a.com/3");
+        assert_eq!(items[2].content.as_deref(), Some("描述"));
+    }
+
+    #[test]
+    fn normalize_news_items_rejects_malformed_data() {
+        assert!(normalize_news_items(&batch(None)).is_err());
+        assert!(normalize_news_items(&batch(Some(json!({"items": "x"})))).is_err());
+        assert_eq!(
+            normalize_news_items(&batch(Some(json!([{"id": "1", "title": "t", "url": "u"}]))))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
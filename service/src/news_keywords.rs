@@ -0,0 +1,195 @@
+use common::api::ApiResponse;
+use common::po::{HealthItem, ItemResult, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use infra::{list_recent_news_item_texts, mark_news_item_extracted, upsert_news_keywords};
+use jieba_rs::Jieba;
+use once_cell::sync::Lazy;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::timer_task_command::task_db_pool;
+
+/// news_keywords.method 取值
+pub const TFIDF_METHOD: &str = "tfidf";
+/// 默认参与计算 IDF 的最近新闻条目数
+const DEFAULT_LIMIT: i64 = 500;
+const MAX_LIMIT: i64 = 5000;
+/// 默认每条新闻保留的关键词数
+const DEFAULT_TOP_K: i64 = 5;
+const MAX_TOP_K: i64 = 20;
+
+/// jieba 默认词典，首次使用时加载
+static JIEBA: Lazy<Jieba> = Lazy::new(Jieba::new);
+
+/// 内置停用词表
+static STOPWORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    include_str!("../assets/stopwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+});
+
+/// 本地 TF-IDF 提取新闻关键词到 news_keywords，不再依赖外部分析服务
+///
+/// params.limit 为参与计算的最近新闻条目数（默认 500），params.top_k 为每条新闻保留的关键词数（默认 5）
+pub async fn native_extract_news_keywords(
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let limit = int_param(&params, "limit", DEFAULT_LIMIT, MAX_LIMIT);
+    let top_k = int_param(&params, "top_k", DEFAULT_TOP_K, MAX_TOP_K) as usize;
+    let pool = task_db_pool()?;
+    let summary = extract_news_keywords_tfidf(pool, limit, top_k).await?;
+
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::ExtractNewsNewsKeywords(HealthItem {
+        url: "native_extract_news_keywords".to_string(),
+        result: summary,
+    });
+    let mut all_items = HashSet::new();
+    all_items.insert(item);
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+fn int_param(params: &Value, name: &str, default: i64, max: i64) -> i64 {
+    params
+        .get(name)
+        .and_then(Value::as_i64)
+        .unwrap_or(default)
+        .clamp(1, max)
+}
+
+/// 以最近 limit 条新闻条目为语料计算 TF-IDF，为其中尚未提取的条目写入 top_k 个关键词并标记已提取
+///
+/// 所有写入在同一事务中完成；返回 `{ items, keywords }` 统计
+pub async fn extract_news_keywords_tfidf(
+    pool: &PgPool,
+    limit: i64,
+    top_k: usize,
+) -> Result<Value, String> {
+    let rows = list_recent_news_item_texts(limit, pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let docs: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let content = row.content.as_deref().unwrap_or_default();
+            tokenize(&format!("{} {content}", row.title))
+        })
+        .collect();
+    let keywords = tfidf_top_k(&docs, top_k);
+
+    let mut items = 0;
+    let mut keyword_count = 0;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    for (row, row_keywords) in rows.iter().zip(&keywords) {
+        if row.extracted {
+            continue;
+        }
+        upsert_news_keywords(row.id, row_keywords, TFIDF_METHOD, &mut tx)
+            .await
+            .map_err(|e| format!("写入关键词失败: {e}"))?;
+        mark_news_item_extracted(row.id, &mut tx)
+            .await
+            .map_err(|e| format!("更新提取状态失败: {e}"))?;
+        items += 1;
+        keyword_count += row_keywords.len();
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    info!("本地提取新闻关键词完成: 条目 {items}，关键词 {keyword_count}");
+    Ok(json!({ "items": items, "keywords": keyword_count }))
+}
+
+/// 中英文混合分词：jieba 词典分词后转小写，去掉停用词、单字和不含文字的片段
+pub fn tokenize(text: &str) -> Vec<String> {
+    JIEBA
+        .cut(text, true)
+        .into_iter()
+        .map(|token| token.trim().to_lowercase())
+        .filter(|token| token.chars().count() >= 2)
+        .filter(|token| token.chars().any(char::is_alphabetic))
+        .filter(|token| !STOPWORDS.contains(token.as_str()))
+        .collect()
+}
+
+/// 计算每篇文档 TF-IDF 权重最高的 top_k 个词，权重相同时按词典序
+pub fn tfidf_top_k(docs: &[Vec<String>], top_k: usize) -> Vec<Vec<(String, f64)>> {
+    let mut weights = tfidf_weights(docs);
+    for doc in &mut weights {
+        doc.truncate(top_k);
+    }
+    weights
+}
+
+/// 计算每篇文档全部词的 TF-IDF 权重，按权重从高到低、权重相同时按词典序排列
+///
+/// tf = 词频 / 文档词数，idf = ln((1 + N) / (1 + df)) + 1
+pub fn tfidf_weights(docs: &[Vec<String>]) -> Vec<Vec<(String, f64)>> {
+    let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+    for doc in docs {
+        let unique: HashSet<&str> = doc.iter().map(String::as_str).collect();
+        for token in unique {
+            *doc_freq.entry(token).or_default() += 1;
+        }
+    }
+    let total_docs = docs.len() as f64;
+
+    docs.iter()
+        .map(|doc| {
+            let mut term_freq: HashMap<&str, usize> = HashMap::new();
+            for token in doc {
+                *term_freq.entry(token.as_str()).or_default() += 1;
+            }
+            let doc_len = doc.len() as f64;
+            let mut weights: Vec<(String, f64)> = term_freq
+                .into_iter()
+                .map(|(token, count)| {
+                    let idf = ((1.0 + total_docs) / (1.0 + doc_freq[token] as f64)).ln() + 1.0;
+                    (token.to_string(), count as f64 / doc_len * idf)
+                })
+                .collect();
+            weights.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            weights
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tfidf_top_k, tokenize};
+
+    #[test]
+    fn tokenize_segments_mixed_text_and_drops_stopwords() {
+        let tokens = tokenize("我们今天发布了 Rust 编程语言的新版本，性能提升 30%");
+
+        assert!(tokens.contains(&"rust".to_string()));
+        assert!(tokens.contains(&"编程语言".to_string()));
+        assert!(tokens.contains(&"性能".to_string()));
+        assert!(!tokens.contains(&"我们".to_string()));
+        assert!(!tokens.contains(&"今天".to_string()));
+        assert!(!tokens.iter().any(|t| t.contains("30")));
+        assert!(tokens.iter().all(|t| t.chars().count() >= 2));
+    }
+
+    #[test]
+    fn tfidf_prefers_terms_unique_to_a_document() {
+        let docs: Vec<Vec<String>> = ["苹果 发布会 手机", "华为 发布会 手机", "苹果 股价 上涨"]
+            .iter()
+            .map(|doc| doc.split(' ').map(str::to_string).collect())
+            .collect();
+
+        let keywords = tfidf_top_k(&docs, 2);
+
+        assert_eq!(keywords.len(), 3);
+        assert_eq!(keywords[1][0].0, "华为");
+        assert!(keywords[1][0].1 > keywords[1][1].1);
+        assert_eq!(keywords[2].len(), 2);
+        assert!(keywords[2].iter().all(|(token, _)| token != "苹果"));
+        assert!(tfidf_top_k(&[Vec::new()], 5)[0].is_empty());
+    }
+}
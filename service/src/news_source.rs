@@ -0,0 +1,110 @@
+use common::dto::NewsSourceDTO;
+use common::po::NewsInfo;
+use infra::{get_news_source, list_news_sources, register_news_source};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// news_source.fetch_kind 取值，与抓取命令对应
+pub const FETCH_KIND_AGGREGATOR: &str = "aggregator";
+pub const FETCH_KIND_RSS: &str = "rss";
+pub const FETCH_KIND_HTML: &str = "html";
+
+/// news_source.display_name 字段长度上限
+const MAX_DISPLAY_NAME_LEN: usize = 50;
+
+/// 读取全部新闻源登记；读取失败时按全部启用、不改名处理，不影响抓取
+pub(crate) async fn load_news_sources(pool: &PgPool) -> HashMap<String, NewsSourceDTO> {
+    match list_news_sources(pool).await {
+        Ok(rows) => rows.into_iter().map(|s| (s.id.clone(), s)).collect(),
+        Err(e) => {
+            warn!("读取新闻源登记失败，本次不做过滤: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+/// 查询单个新闻源登记，失败时同样视为未登记
+pub(crate) async fn lookup_news_source(id: &str, pool: &PgPool) -> Option<NewsSourceDTO> {
+    get_news_source(id, pool).await.unwrap_or_else(|e| {
+        warn!("读取新闻源 {id} 登记失败: {e}");
+        None
+    })
+}
+
+/// 已登记且停用的源不再抓取
+pub(crate) fn is_disabled(source: Option<&NewsSourceDTO>) -> bool {
+    source.is_some_and(|s| !s.enabled)
+}
+
+/// 按登记表整理抓取结果：已登记的源改用登记名称，未登记的源按抓取到的名称自动登记
+pub(crate) async fn apply_news_source(
+    news: NewsInfo,
+    source: Option<&NewsSourceDTO>,
+    fetch_kind: &str,
+    url: &str,
+    pool: &PgPool,
+) -> NewsInfo {
+    match source {
+        Some(source) => rename_news(news, source),
+        None => {
+            let name: String = news.name.chars().take(MAX_DISPLAY_NAME_LEN).collect();
+            if let Err(e) = register_news_source(&news.id, &name, fetch_kind, url, pool).await {
+                warn!("自动登记新闻源 {} 失败: {e}", news.id);
+            }
+            news
+        }
+    }
+}
+
+/// 登记名称非空时覆盖上游返回的名称
+fn rename_news(news: NewsInfo, source: &NewsSourceDTO) -> NewsInfo {
+    match source.display_name.trim() {
+        "" => news,
+        name => NewsInfo {
+            name: name.to_string(),
+            ..news
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_disabled, rename_news};
+    use chrono::Utc;
+    use common::dto::NewsSourceDTO;
+    use common::po::NewsInfo;
+
+    fn source(display_name: &str, enabled: bool) -> NewsSourceDTO {
+        NewsSourceDTO {
+            id: "weibo".to_string(),
+            display_name: display_name.to_string(),
+            category: Some("社交".to_string()),
+            enabled,
+            fetch_kind: "aggregator".to_string(),
+            url: None,
+            priority: 0,
+            language: "zh".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn registry_overrides_name_and_disables_sources() {
+        let news = NewsInfo {
+            id: "weibo".to_string(),
+            name: "微博".to_string(),
+            items: Vec::new(),
+        };
+
+        assert_eq!(
+            rename_news(news.clone(), &source("微博热搜", true)).name,
+            "微博热搜"
+        );
+        assert_eq!(rename_news(news, &source("  ", true)).name, "微博");
+        assert!(is_disabled(Some(&source("微博", false))));
+        assert!(!is_disabled(Some(&source("微博", true))));
+        assert!(!is_disabled(None));
+    }
+}
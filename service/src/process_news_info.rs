@@ -1,11 +1,20 @@
+use chrono::NaiveDate;
 use common::api::ApiResponse;
-use common::po::{HealthItem, ItemResult, NewsInfo, TaskItem};
+use common::po::{HealthItem, HttpFetchCache, ItemResult, NewsInfo, TaskItem};
 use common::utils::date_utils::get_today_weekday;
+use infra::{current_news_date, list_http_fetch_cache, upsert_http_fetch_cache, upsert_news_info};
 use once_cell::sync::Lazy;
-use reqwest::{Client, Url};
+use reqwest::{Client, StatusCode, Url, header};
 use serde_json::json;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use tracing::{error, info, warn};
+
+use crate::news_source::{
+    FETCH_KIND_AGGREGATOR, apply_news_source, is_disabled, load_news_sources,
+};
+use crate::rss_feed::fnv1a_64;
+use crate::timer_task_command::task_db_pool;
 
 /// 全局共享的 HTTP client（内部已 Arc 化，clone() 代价极低）
 pub(crate) static HTTP_CLIENT: Lazy<Client> = Lazy::new(Client::new);
@@ -93,7 +102,11 @@ pub async fn merge_cross_day_news_events(
 /// base_url 格式: https://news.likanug.top
 /// 步骤：1. GET /api/s/ids 获取所有 sourceId
 ///       2. 并发 GET /api/s?id={sourceId} 获取每个源数据
+///
+/// 按源地址在 http_fetch_cache 中记录 ETag / Last-Modified / 内容哈希：当天已写入过的源带条件请求头，
+/// 返回 304 或条目内容与已写入的一致时跳过写入。结果为 `{ sources, changed, skipped, not_modified, unchanged, failed }` 统计
 pub async fn fetch_all_news(base_url: String) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let pool = task_db_pool()?;
     let ids_url = format!("{base_url}/api/s/ids");
     let ids: Vec<String> = HTTP_CLIENT
         .get(&ids_url)
@@ -104,43 +117,301 @@ pub async fn fetch_all_news(base_url: String) -> anyhow::Result<ApiResponse<Item
         .await
         .map_err(|e| format!("解析 ids 响应失败: {e}"))?;
 
-    let weekday = get_today_weekday().name_cn.to_string();
-    let mut all_items: HashSet<TaskItem> = HashSet::new();
+    let sources = load_news_sources(pool).await;
+    let total_ids = ids.len();
+    let ids: Vec<String> = ids
+        .into_iter()
+        .filter(|id| !is_disabled(sources.get(id)))
+        .collect();
+    let disabled = total_ids - ids.len();
+
+    let urls: Vec<String> = ids
+        .iter()
+        .map(|id| format!("{base_url}/api/s?id={id}"))
+        .collect();
+    let mut caches: HashMap<String, HttpFetchCache> = match list_http_fetch_cache(&urls, pool).await
+    {
+        Ok(rows) => rows.into_iter().map(|c| (c.url.clone(), c)).collect(),
+        Err(e) => {
+            warn!("读取抓取缓存失败，本次不做条件请求: {e}");
+            HashMap::new()
+        }
+    };
+    // 与 news_info.news_date 的默认值取同一来源，避免应用与数据库时区不同时跨天判断错位
+    let today = current_news_date(pool).await.map_err(|e| e.to_string())?;
 
     // 并发抓取所有源
-    let tasks: Vec<_> = ids
+    let tasks: Vec<_> = urls
         .into_iter()
-        .map(|id| {
-            let url = format!("{base_url}/api/s?id={id}");
-            let client = HTTP_CLIENT.clone();
+        .map(|url| {
+            let cache = caches.remove(&url);
             tokio::spawn(async move {
-                let resp = client.get(&url).send().await.ok()?;
-                if !resp.status().is_success() {
-                    return None;
-                }
-                let json_value: serde_json::Value = resp.json().await.ok()?;
-                let source_id = json_value["id"].as_str()?.to_string();
-                let name = json_value["name"]
-                    .as_str()
-                    .unwrap_or(&source_id)
-                    .to_string();
-                let items = json_value["items"].as_array().cloned().unwrap_or_default();
-                Some(NewsInfo {
-                    id: source_id,
-                    name,
-                    items,
-                })
+                let fetched = fetch_source(&url, cache, today).await;
+                (url, fetched)
             })
         })
         .collect();
 
+    let (mut changed, mut not_modified, mut unchanged, mut failed) = (0, 0, 0, 0);
     for task in tasks {
-        if let Ok(Some(news)) = task.await {
-            all_items.insert(TaskItem::News(news));
+        let cache = match task.await {
+            Ok((_, Ok(SourceFetch::NotModified(cache)))) => {
+                not_modified += 1;
+                cache
+            }
+            Ok((_, Ok(SourceFetch::Unchanged(cache)))) => {
+                unchanged += 1;
+                cache
+            }
+            Ok((url, Ok(SourceFetch::Changed(news, cache)))) => {
+                let source = sources.get(&news.id);
+                let news = apply_news_source(news, source, FETCH_KIND_AGGREGATOR, &url, pool).await;
+                match upsert_news_info(&news, pool).await {
+                    Ok(true) => changed += 1,
+                    Ok(false) => unchanged += 1,
+                    Err(e) => {
+                        warn!("保存新闻源 {url} 失败: {e}");
+                        failed += 1;
+                        continue;
+                    }
+                }
+                cache
+            }
+            Ok((url, Err(e))) => {
+                warn!("抓取新闻源 {url} 失败: {e}");
+                failed += 1;
+                continue;
+            }
+            Err(e) => {
+                error!("抓取任务执行失败: {e}");
+                failed += 1;
+                continue;
+            }
+        };
+        if let Err(e) = upsert_http_fetch_cache(&cache, pool).await {
+            warn!("更新 {} 的抓取缓存失败: {e}", cache.url);
         }
     }
 
-    let mut result = HashMap::new();
-    result.insert(weekday, all_items);
-    Ok(ApiResponse::ok(result))
+    info!(
+        "抓取新闻源完成: 共 {total_ids}，变化 {changed}，未修改 {not_modified}，内容未变 {unchanged}，停用 {disabled}，失败 {failed}"
+    );
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::FetchNews(HealthItem {
+        url: base_url,
+        result: json!({
+            "sources": total_ids,
+            "changed": changed,
+            "skipped": not_modified + unchanged,
+            "not_modified": not_modified,
+            "unchanged": unchanged,
+            "disabled": disabled,
+            "failed": failed,
+        }),
+    });
+    Ok(ApiResponse::ok(build_single_item_result(weekday, item)))
+}
+
+/// 单个新闻源的抓取结果，均带上需要写回的抓取缓存
+#[derive(Debug, PartialEq)]
+enum SourceFetch {
+    /// 条件请求返回 304
+    NotModified(HttpFetchCache),
+    /// 条目内容与当天已写入的一致
+    Unchanged(HttpFetchCache),
+    /// 需要写入 news_info，写入成功后再更新缓存
+    Changed(NewsInfo, HttpFetchCache),
+}
+
+async fn fetch_source(
+    url: &str,
+    cache: Option<HttpFetchCache>,
+    today: NaiveDate,
+) -> Result<SourceFetch, String> {
+    let mut request = HTTP_CLIENT.get(url);
+    for (name, value) in conditional_headers(cache.as_ref(), today) {
+        request = request.header(name, value);
+    }
+    let resp = request.send().await.map_err(|e| e.to_string())?;
+    let header = |name: header::HeaderName| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(header::ETAG);
+    let last_modified = header(header::LAST_MODIFIED);
+
+    if resp.status() == StatusCode::NOT_MODIFIED {
+        let cache = cache.ok_or("未发送条件请求却返回 304")?;
+        return Ok(SourceFetch::NotModified(HttpFetchCache {
+            etag: etag.or(cache.etag.clone()),
+            last_modified: last_modified.or(cache.last_modified.clone()),
+            ..cache
+        }));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("状态码: {}", resp.status()));
+    }
+    let json_value: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let news = parse_source(&json_value).ok_or("响应中缺少 id")?;
+    Ok(classify_source(
+        url,
+        news,
+        cache.as_ref(),
+        etag,
+        last_modified,
+        today,
+    ))
+}
+
+/// 当天已写入过的源才带条件请求头；跨天后需要完整数据写入当天的新批次
+fn conditional_headers(
+    cache: Option<&HttpFetchCache>,
+    today: NaiveDate,
+) -> Vec<(header::HeaderName, String)> {
+    let Some(cache) = cache.filter(|c| c.stored_date == Some(today)) else {
+        return Vec::new();
+    };
+    let mut headers = Vec::new();
+    if let Some(etag) = &cache.etag {
+        headers.push((header::IF_NONE_MATCH, etag.clone()));
+    }
+    if let Some(last_modified) = &cache.last_modified {
+        headers.push((header::IF_MODIFIED_SINCE, last_modified.clone()));
+    }
+    headers
+}
+
+fn parse_source(json_value: &serde_json::Value) -> Option<NewsInfo> {
+    let source_id = json_value["id"].as_str()?.to_string();
+    let name = json_value["name"]
+        .as_str()
+        .unwrap_or(&source_id)
+        .to_string();
+    let items = json_value["items"].as_array().cloned().unwrap_or_default();
+    Some(NewsInfo {
+        id: source_id,
+        name,
+        items,
+    })
+}
+
+/// 按条目内容哈希判断源数据是否变化；只与当天写入过的内容比较
+fn classify_source(
+    url: &str,
+    news: NewsInfo,
+    cache: Option<&HttpFetchCache>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    today: NaiveDate,
+) -> SourceFetch {
+    let content_hash = format!(
+        "{:016x}",
+        fnv1a_64(&serde_json::to_string(&news.items).unwrap_or_default())
+    );
+    let unchanged = cache.is_some_and(|c| {
+        c.stored_date == Some(today) && c.content_hash.as_deref() == Some(content_hash.as_str())
+    });
+    let cache = HttpFetchCache {
+        url: url.to_string(),
+        etag,
+        last_modified,
+        content_hash: Some(content_hash),
+        stored_date: Some(today),
+    };
+    if unchanged {
+        SourceFetch::Unchanged(cache)
+    } else {
+        SourceFetch::Changed(news, cache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SourceFetch, classify_source, conditional_headers};
+    use chrono::NaiveDate;
+    use common::po::{HttpFetchCache, NewsInfo};
+    use reqwest::header;
+    use serde_json::json;
+
+    const URL: &str = "https://news.example.com/api/s?id=weibo";
+
+    fn news(title: &str) -> NewsInfo {
+        NewsInfo {
+            id: "weibo".to_string(),
+            name: "微博".to_string(),
+            items: vec![json!({"id": "1", "title": title, "url": "https://a.com/1"})],
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 9, d).unwrap()
+    }
+
+    #[test]
+    fn classify_source_skips_content_already_stored_today() {
+        let SourceFetch::Changed(_, first) = classify_source(
+            URL,
+            news("标题"),
+            None,
+            Some("\"v1\"".into()),
+            None,
+            day(16),
+        ) else {
+            panic!("首次抓取应视为变化");
+        };
+        assert_eq!(first.stored_date, Some(day(16)));
+        assert_eq!(first.content_hash.as_ref().unwrap().len(), 16);
+
+        // 同一天内容相同：跳过写入，但记录新的 ETag
+        let same = classify_source(
+            URL,
+            news("标题"),
+            Some(&first),
+            Some("\"v2\"".into()),
+            None,
+            day(16),
+        );
+        let SourceFetch::Unchanged(cache) = same else {
+            panic!("内容未变应跳过");
+        };
+        assert_eq!(cache.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(cache.content_hash, first.content_hash);
+
+        // 内容变化或跨天都需要写入
+        assert!(matches!(
+            classify_source(URL, news("新标题"), Some(&first), None, None, day(16)),
+            SourceFetch::Changed(..)
+        ));
+        assert!(matches!(
+            classify_source(URL, news("标题"), Some(&first), None, None, day(17)),
+            SourceFetch::Changed(..)
+        ));
+    }
+
+    #[test]
+    fn conditional_headers_only_for_sources_stored_today() {
+        let cache = HttpFetchCache {
+            url: URL.to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Tue, 16 Sep 2025 08:00:00 GMT".to_string()),
+            content_hash: Some("0123456789abcdef".to_string()),
+            stored_date: Some(day(16)),
+        };
+
+        let headers = conditional_headers(Some(&cache), day(16));
+        assert_eq!(
+            headers,
+            vec![
+                (header::IF_NONE_MATCH, "\"abc\"".to_string()),
+                (
+                    header::IF_MODIFIED_SINCE,
+                    "Tue, 16 Sep 2025 08:00:00 GMT".to_string()
+                ),
+            ]
+        );
+        assert!(conditional_headers(Some(&cache), day(17)).is_empty());
+        assert!(conditional_headers(None, day(16)).is_empty());
+    }
 }
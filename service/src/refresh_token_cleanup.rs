@@ -0,0 +1,60 @@
+use common::api::ApiResponse;
+use common::po::{HealthItem, ItemResult, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use infra::delete_expired_refresh_tokens;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::timer_task_command::task_db_pool;
+
+/// 默认在过期 1 天后删除 refresh_token
+const DEFAULT_RETENTION_DAYS: i64 = 1;
+const MAX_RETENTION_DAYS: i64 = 365;
+
+/// 删除过期超过 params.retention_days 天（默认 1）的 refresh_token
+///
+/// 已吊销但尚未过期的 token 不删除，refresh 接口依赖它们识别旧 token 被重放
+pub async fn cleanup_expired_refresh_tokens(
+    params: Value,
+) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let retention_days = retention_days(&params);
+    let pool = task_db_pool()?;
+    let deleted = delete_expired_refresh_tokens(retention_days, pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    info!("清理过期 refresh_token 完成: 删除 {deleted} 条");
+
+    let weekday = get_today_weekday().name_cn.to_string();
+    let item = TaskItem::Maintenance(HealthItem {
+        url: "cleanup_expired_refresh_tokens".to_string(),
+        result: json!({ "deleted": deleted, "retentionDays": retention_days }),
+    });
+    let mut all_items = HashSet::new();
+    all_items.insert(item);
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+fn retention_days(params: &Value) -> i64 {
+    params
+        .get("retention_days")
+        .and_then(Value::as_i64)
+        .unwrap_or(DEFAULT_RETENTION_DAYS)
+        .clamp(0, MAX_RETENTION_DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::retention_days;
+    use serde_json::json;
+
+    #[test]
+    fn retention_days_defaults_and_clamps() {
+        assert_eq!(retention_days(&json!({})), 1);
+        assert_eq!(retention_days(&json!({"retention_days": 7})), 7);
+        assert_eq!(retention_days(&json!({"retention_days": -3})), 0);
+        assert_eq!(retention_days(&json!({"retention_days": 10000})), 365);
+    }
+}
@@ -59,7 +59,7 @@ pub async fn github_user_register(
             avatar_url: github_user.avatar_url,
             r#type: "github".to_string(),
             roles: user.roles,
-            ver: 0,
+            ver: user.token_version,
         },
         access_token_mins,
     )?;
@@ -0,0 +1,355 @@
+use chrono::{DateTime, FixedOffset};
+use common::api::ApiResponse;
+use common::po::{ItemResult, NewsInfo, TaskItem};
+use common::utils::date_utils::get_today_weekday;
+use reqwest::Url;
+use roxmltree::{Document, Node};
+use scraper::Html;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+use crate::news_source::{FETCH_KIND_RSS, apply_news_source, is_disabled, lookup_news_source};
+use crate::process_news_info::HTTP_CLIENT;
+use crate::timer_task_command::task_db_pool;
+
+/// news_info.news_from 字段长度上限
+const MAX_SOURCE_ID_LEN: usize = 50;
+/// 条目摘要保留的最大字符数
+const MAX_SUMMARY_CHARS: usize = 200;
+
+/// 抓取 RSS 2.0 / RSS 1.0 / Atom 订阅源，整理成一条 NewsInfo 存入 news_info 表
+/// feed_url 格式: https://example.com/feed.xml
+///
+/// news_source 中停用的源直接跳过，已登记的源改用登记名称，未登记的源自动登记
+pub async fn fetch_rss(feed_url: String) -> anyhow::Result<ApiResponse<ItemResult>, String> {
+    let pool = task_db_pool()?;
+    let weekday = get_today_weekday().name_cn.to_string();
+    let source = lookup_news_source(&source_id(FETCH_KIND_RSS, &feed_url), pool).await;
+    if is_disabled(source.as_ref()) {
+        info!("订阅源 {feed_url} 已停用，跳过抓取");
+        return Ok(ApiResponse::ok(HashMap::from([(weekday, HashSet::new())])));
+    }
+
+    let response = HTTP_CLIENT
+        .get(&feed_url)
+        .header(
+            "Accept",
+            "application/rss+xml, application/atom+xml, application/xml, text/xml",
+        )
+        .send()
+        .await
+        .map_err(|e| format!("获取订阅源失败 {feed_url}: {e}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "HTTP错误: {} - 状态码: {}",
+            feed_url,
+            response.status()
+        ));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("读取订阅源响应失败 {feed_url}: {e}"))?;
+    let news = parse_feed(&body, &feed_url)?;
+    let news = apply_news_source(news, source.as_ref(), FETCH_KIND_RSS, &feed_url, pool).await;
+
+    let mut all_items = HashSet::new();
+    all_items.insert(TaskItem::News(news));
+    let mut result = HashMap::new();
+    result.insert(weekday, all_items);
+    Ok(ApiResponse::ok(result))
+}
+
+/// 解析订阅源 XML
+///
+/// 条目结构与聚合源保持一致：`{ id, title, url, pubDate?, extra? }`，
+/// 其中 pubDate 为毫秒时间戳，extra.hover 为纯文本摘要
+pub fn parse_feed(xml: &str, feed_url: &str) -> Result<NewsInfo, String> {
+    let doc = Document::parse(xml).map_err(|e| format!("解析订阅源 XML 失败 {feed_url}: {e}"))?;
+    let root = doc.root_element();
+
+    let (title, entries): (Option<String>, Vec<Value>) = match root.tag_name().name() {
+        "rss" => {
+            let channel = child(root, "channel").ok_or("RSS 缺少 channel 节点")?;
+            let entries = children(channel, "item")
+                .filter_map(|item| rss_entry(item, feed_url))
+                .collect();
+            (child_text(channel, "title"), entries)
+        }
+        // RSS 1.0 的 item 与 channel 同级
+        "RDF" => {
+            let title = child(root, "channel").and_then(|c| child_text(c, "title"));
+            let entries = children(root, "item")
+                .filter_map(|item| rss_entry(item, feed_url))
+                .collect();
+            (title, entries)
+        }
+        "feed" => {
+            let entries = children(root, "entry")
+                .filter_map(|entry| atom_entry(entry, feed_url))
+                .collect();
+            (child_text(root, "title"), entries)
+        }
+        other => return Err(format!("不支持的订阅源格式 <{other}>: {feed_url}")),
+    };
+
+    let id = source_id(FETCH_KIND_RSS, feed_url);
+    Ok(NewsInfo {
+        name: title.unwrap_or_else(|| id.clone()),
+        id,
+        items: entries,
+    })
+}
+
+fn rss_entry(item: Node, feed_url: &str) -> Option<Value> {
+    let title = child_text(item, "title");
+    let link = child_text(item, "link").or_else(|| {
+        // RSS 1.0 的链接放在 rdf:about 属性上
+        item.attributes()
+            .find(|attr| attr.name() == "about")
+            .map(|attr| attr.value().trim().to_string())
+    });
+    let guid = child_text(item, "guid");
+    let published = child_text(item, "pubDate")
+        .and_then(|s| DateTime::parse_from_rfc2822(&s).ok())
+        .or_else(|| child_text(item, "date").and_then(|s| parse_rfc3339(&s)));
+    let summary = child_text(item, "description");
+
+    build_entry(title, link, guid, published, summary, feed_url)
+}
+
+fn atom_entry(entry: Node, feed_url: &str) -> Option<Value> {
+    let title = child_text(entry, "title");
+    let link = children(entry, "link")
+        .find(|link| matches!(link.attribute("rel"), None | Some("alternate")))
+        .and_then(|link| link.attribute("href"))
+        .map(|href| href.trim().to_string())
+        .filter(|href| !href.is_empty());
+    let id = child_text(entry, "id");
+    let published = child_text(entry, "published")
+        .or_else(|| child_text(entry, "updated"))
+        .and_then(|s| parse_rfc3339(&s));
+    let summary = child_text(entry, "summary").or_else(|| child_text(entry, "content"));
+
+    build_entry(title, link, id, published, summary, feed_url)
+}
+
+/// 组装单个条目；标题为空的条目直接丢弃
+///
+/// 条目 id 依次取 guid / Atom id、链接，都缺失时用标题和发布时间计算哈希，保证重复抓取时稳定
+fn build_entry(
+    title: Option<String>,
+    link: Option<String>,
+    guid: Option<String>,
+    published: Option<DateTime<FixedOffset>>,
+    summary: Option<String>,
+    feed_url: &str,
+) -> Option<Value> {
+    let title = title.map(|t| html_to_text(&t)).filter(|t| !t.is_empty())?;
+    let url = link
+        .map(|link| resolve_link(feed_url, &link))
+        .unwrap_or_default();
+    let id = guid
+        .or_else(|| (!url.is_empty()).then(|| url.clone()))
+        .unwrap_or_else(|| {
+            let published = published.map(|p| p.to_rfc3339()).unwrap_or_default();
+            format!("{:016x}", fnv1a_64(&format!("{title}\n{published}")))
+        });
+
+    let mut entry = Map::new();
+    entry.insert("id".to_string(), json!(id));
+    entry.insert("title".to_string(), json!(title));
+    entry.insert("url".to_string(), json!(url));
+    if let Some(published) = published {
+        entry.insert("pubDate".to_string(), json!(published.timestamp_millis()));
+    }
+    if let Some(summary) = summary
+        .map(|s| truncate_chars(&html_to_text(&s), MAX_SUMMARY_CHARS))
+        .filter(|s| !s.is_empty())
+    {
+        entry.insert("extra".to_string(), json!({ "hover": summary }));
+    }
+    Some(Value::Object(entry))
+}
+
+/// 抓取源在 news_info 中的来源标识：`{prefix}:{host}:{url 哈希}`，同一站点的多个抓取源互不覆盖
+pub(crate) fn source_id(prefix: &str, source_url: &str) -> String {
+    let host = Url::parse(source_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    let hash = format!("{:08x}", fnv1a_64(source_url) as u32);
+    // 预留 "{prefix}:" 和 ":{hash}" 的长度
+    let host: String = host
+        .chars()
+        .take(MAX_SOURCE_ID_LEN.saturating_sub(prefix.len() + 2 + hash.len()))
+        .collect();
+    format!("{prefix}:{host}:{hash}")
+}
+
+/// 相对链接按抓取地址补全
+pub(crate) fn resolve_link(base_url: &str, link: &str) -> String {
+    Url::parse(base_url)
+        .and_then(|base| base.join(link))
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| link.to_string())
+}
+
+pub(crate) fn parse_rfc3339(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(s).ok()
+}
+
+/// 去掉 HTML 标签并压缩空白
+pub(crate) fn html_to_text(s: &str) -> String {
+    let fragment = Html::parse_fragment(s);
+    fragment
+        .root_element()
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}…", &s[..idx]),
+        None => s.to_string(),
+    }
+}
+
+/// FNV-1a 哈希，结果不随 Rust 版本变化，可用于持久化的标识
+pub(crate) fn fnv1a_64(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// 子节点文本（含 CDATA），去掉首尾空白，空文本视为缺失
+fn child_text(node: Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_feed, source_id};
+    use serde_json::json;
+
+    const RSS_FIXTURE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>示例技术周刊</title>
+    <link>https://tech.example.com/</link>
+    <item>
+      <title><![CDATA[Rust 1.90 发布]]></title>
+      <link>https://tech.example.com/posts/rust-190</link>
+      <guid isPermaLink="false">post-190</guid>
+      <pubDate>Tue, 16 Sep 2025 08:00:00 +0800</pubDate>
+      <description><![CDATA[<p>新版本带来 <b>更快</b> 的编译速度</p>]]></description>
+    </item>
+    <item>
+      <title>相对链接</title>
+      <link>/posts/relative</link>
+      <dc:date>2025-09-15T10:00:00Z</dc:date>
+    </item>
+    <item>
+      <description>没有标题的条目会被丢弃</description>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM_FIXTURE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>番剧资讯</title>
+  <entry>
+    <title type="html">新番定档</title>
+    <link rel="self" href="https://anime.example.com/api/1"/>
+    <link rel="alternate" href="https://anime.example.com/news/1"/>
+    <id>tag:anime.example.com,2025:1</id>
+    <updated>2025-09-16T12:30:00+09:00</updated>
+    <summary>十月新番列表公布</summary>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parse_feed_maps_rss_items() {
+        let feed_url = "https://tech.example.com/feed.xml";
+        let news = parse_feed(RSS_FIXTURE, feed_url).unwrap();
+
+        assert_eq!(news.name, "示例技术周刊");
+        assert_eq!(news.id, source_id("rss", feed_url));
+        assert_eq!(news.items.len(), 2);
+        assert_eq!(
+            news.items[0],
+            json!({
+                "id": "post-190",
+                "title": "Rust 1.90 发布",
+                "url": "https://tech.example.com/posts/rust-190",
+                "pubDate": 1757980800000i64,
+                "extra": { "hover": "新版本带来 更快 的编译速度" }
+            })
+        );
+        // 没有 guid 时以补全后的链接作为 id
+        assert_eq!(
+            news.items[1]["id"],
+            "https://tech.example.com/posts/relative"
+        );
+        assert_eq!(news.items[1]["pubDate"], 1757930400000i64);
+    }
+
+    #[test]
+    fn parse_feed_maps_atom_entries() {
+        let news = parse_feed(ATOM_FIXTURE, "https://anime.example.com/atom").unwrap();
+
+        assert_eq!(news.name, "番剧资讯");
+        assert_eq!(
+            news.items,
+            vec![json!({
+                "id": "tag:anime.example.com,2025:1",
+                "title": "新番定档",
+                "url": "https://anime.example.com/news/1",
+                "pubDate": 1757993400000i64,
+                "extra": { "hover": "十月新番列表公布" }
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_feed_rejects_unknown_documents() {
+        assert!(parse_feed("<html><body/></html>", "https://a.com/feed").is_err());
+        assert!(parse_feed("not xml", "https://a.com/feed").is_err());
+    }
+
+    #[test]
+    fn source_id_is_stable_and_bounded() {
+        let id = source_id("rss", "https://tech.example.com/feed.xml");
+        assert!(id.starts_with("rss:tech.example.com:"));
+        assert_eq!(id, source_id("rss", "https://tech.example.com/feed.xml"));
+        assert_ne!(id, source_id("rss", "https://tech.example.com/atom.xml"));
+
+        let long_host = format!("https://{}.example.com/feed", "a".repeat(80));
+        assert!(source_id("html", &long_host).len() <= 50);
+    }
+}
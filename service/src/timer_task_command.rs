@@ -1,21 +1,37 @@
+use chrono::NaiveDate;
 use common::api::ApiResponse;
 use common::po::ItemResult;
+use regex::Regex;
+use reqwest::Url;
+use scraper::Selector;
+use serde_json::Value;
+use sqlx::PgPool;
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+use crate::ani_schedule::{fetch_bilibili_ani, fetch_iqiyi_ani};
 use crate::health_checker::health_check;
+use crate::html_scraper::scrape_html;
+use crate::news_event_cluster::native_extract_news_event;
+use crate::news_event_merge::native_merge_news_events;
+use crate::news_extractor::native_extract_news_item;
+use crate::news_keywords::native_extract_news_keywords;
 use crate::process_news_info::{
     extract_news_event, extract_news_item, extract_news_keywords, fetch_all_news,
     merge_cross_day_news_events,
 };
+use crate::refresh_token_cleanup::cleanup_expired_refresh_tokens;
+use crate::rss_feed::fetch_rss;
 
 /// 通用命令输入参数，可以传任意 JSON 数据
 #[derive(Clone)]
 pub struct CommandInput {
     pub urls: Option<String>,
     pub args: String,
+    /// 任务完整的 params
+    pub params: Value,
 }
 
 /// CmdFn 表示：接收 CommandInput，返回一个 boxed future，输出为 Result<ApiResponse<ItemResult>, String>
@@ -27,39 +43,613 @@ pub type CmdFn = Arc<
         + Sync,
 >;
 
-/// 构建命令表，将异步函数包装成 CmdFn
-pub fn build_cmd_map() -> HashMap<String, CmdFn> {
-    let mut map: HashMap<String, CmdFn> = HashMap::new();
+/// 命令参数的取值类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    String,
+    Integer,
+    /// 整数或小数
+    Number,
+    Boolean,
+    /// YYYY-MM-DD 格式的日期
+    Date,
+    /// http / https 绝对地址
+    Url,
+    /// 英文逗号分隔的多个 http / https 地址
+    UrlList,
+    /// CSS 选择器
+    Selector,
+    /// 正则表达式
+    Regex,
+}
+
+impl ParamKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamKind::String => "string",
+            ParamKind::Integer => "integer",
+            ParamKind::Number => "number",
+            ParamKind::Boolean => "boolean",
+            ParamKind::Date => "date",
+            ParamKind::Url => "url",
+            ParamKind::UrlList => "url_list",
+            ParamKind::Selector => "css_selector",
+            ParamKind::Regex => "regex",
+        }
+    }
+
+    fn check(&self, value: &Value) -> Result<(), String> {
+        match self {
+            ParamKind::String => match value.as_str() {
+                Some(s) if !s.trim().is_empty() => Ok(()),
+                Some(_) => Err("不能为空".to_string()),
+                None => Err("必须为字符串".to_string()),
+            },
+            ParamKind::Integer => value
+                .as_i64()
+                .map(|_| ())
+                .ok_or_else(|| "必须为整数".to_string()),
+            ParamKind::Number => value
+                .as_f64()
+                .map(|_| ())
+                .ok_or_else(|| "必须为数字".to_string()),
+            ParamKind::Boolean => value
+                .as_bool()
+                .map(|_| ())
+                .ok_or_else(|| "必须为布尔值".to_string()),
+            ParamKind::Date => {
+                let s = value.as_str().ok_or_else(|| "必须为字符串".to_string())?;
+                NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                    .map(|_| ())
+                    .map_err(|_| format!("不是 YYYY-MM-DD 格式的日期: '{s}'"))
+            }
+            ParamKind::Url => {
+                let s = value.as_str().ok_or_else(|| "必须为字符串".to_string())?;
+                check_http_url(s.trim())
+            }
+            ParamKind::UrlList => {
+                let s = value.as_str().ok_or_else(|| "必须为字符串".to_string())?;
+                s.split(',').try_for_each(|url| check_http_url(url.trim()))
+            }
+            ParamKind::Selector => {
+                let s = value.as_str().ok_or_else(|| "必须为字符串".to_string())?;
+                Selector::parse(s.trim())
+                    .map(|_| ())
+                    .map_err(|e| format!("不是合法的 CSS 选择器 '{s}': {e}"))
+            }
+            ParamKind::Regex => {
+                let s = value.as_str().ok_or_else(|| "必须为字符串".to_string())?;
+                Regex::new(s)
+                    .map(|_| ())
+                    .map_err(|e| format!("不是合法的正则 '{s}': {e}"))
+            }
+        }
+    }
+}
+
+fn check_http_url(s: &str) -> Result<(), String> {
+    if s.is_empty() {
+        return Err("不能为空".to_string());
+    }
+    let url = Url::parse(s).map_err(|e| format!("不是合法的 URL '{s}': {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(format!("只支持 http / https 地址: '{s}'"));
+    }
+    Ok(())
+}
+
+/// 命令参数定义，对应任务 params 中的同名字段
+#[derive(Clone, Debug)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+impl ParamSpec {
+    pub fn required(name: &'static str, kind: ParamKind, description: &'static str) -> Self {
+        Self {
+            name,
+            kind,
+            required: true,
+            description,
+        }
+    }
+
+    pub fn optional(name: &'static str, kind: ParamKind, description: &'static str) -> Self {
+        Self {
+            name,
+            kind,
+            required: false,
+            description,
+        }
+    }
+}
+
+/// 参数校验错误，field 为 params 中的字段名
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "params.{} {}", self.field, self.message)
+    }
+}
+
+/// 命令定义：参数 schema 和执行函数
+#[derive(Clone)]
+pub struct Command {
+    pub description: &'static str,
+    /// 失败时是否按任务的 retry_times 重试；每次执行都会留下记录的命令（如健康检测写历史）不重试
+    pub retryable: bool,
+    pub params: Vec<ParamSpec>,
+    pub handler: CmdFn,
+}
+
+impl Command {
+    /// 按参数 schema 校验任务 params，schema 之外的字段（如执行控制参数）不做限制
+    pub fn validate_params(&self, params: &Value) -> Result<(), ParamError> {
+        for spec in &self.params {
+            match params.get(spec.name) {
+                None | Some(Value::Null) if spec.required => {
+                    return Err(ParamError {
+                        field: spec.name.to_string(),
+                        message: "为必填项".to_string(),
+                    });
+                }
+                None | Some(Value::Null) => {}
+                Some(value) => spec.kind.check(value).map_err(|message| ParamError {
+                    field: spec.name.to_string(),
+                    message,
+                })?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 构建命令表，将异步函数包装成 CmdFn 并声明各命令的参数
+pub fn build_cmd_map() -> HashMap<String, Command> {
+    let mut map: HashMap<String, Command> = HashMap::new();
 
     map.insert(
         "health_check".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(health_check(input.args))),
+        Command {
+            description: "检测服务健康状态",
+            retryable: false,
+            params: vec![
+                ParamSpec::required(
+                    "arg",
+                    ParamKind::UrlList,
+                    "健康检查地址，多个用英文逗号分隔",
+                ),
+                ParamSpec::optional(
+                    "expect_status",
+                    ParamKind::Integer,
+                    "期望的 HTTP 状态码，默认要求 2xx",
+                ),
+                ParamSpec::optional(
+                    "max_latency_ms",
+                    ParamKind::Integer,
+                    "允许的最大耗时（毫秒）",
+                ),
+                ParamSpec::optional(
+                    "expect_json",
+                    ParamKind::Boolean,
+                    "是否要求响应为 JSON，默认 true",
+                ),
+                ParamSpec::optional(
+                    "json_path",
+                    ParamKind::String,
+                    "断言取值的 JSONPath，如 $.data.status",
+                ),
+                ParamSpec::optional(
+                    "json_equals",
+                    ParamKind::String,
+                    "JSONPath 取值应等于的内容",
+                ),
+                ParamSpec::optional(
+                    "json_contains",
+                    ParamKind::String,
+                    "JSONPath 取值应包含的内容",
+                ),
+                ParamSpec::optional("body_regex", ParamKind::Regex, "响应正文应匹配的正则"),
+            ],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(health_check(input.args, input.params))
+            }),
+        },
     );
 
     map.insert(
         "extract_transform_news_info_to_item".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(extract_news_item(input.args))),
+        Command {
+            description: "提取 news_info 到 news_item",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "分析服务提取接口地址",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(extract_news_item(input.args))),
+        },
+    );
+
+    map.insert(
+        "native_extract_news_item".to_string(),
+        Command {
+            description: "本地抽取 news_info 到 news_item",
+            retryable: true,
+            params: vec![ParamSpec::optional(
+                "batch_size",
+                ParamKind::Integer,
+                "单次处理的 news_info 批次数，默认 50，最多 500",
+            )],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(native_extract_news_item(input.params))
+            }),
+        },
     );
 
     map.insert(
         "extract_keywords_to_news_keywords".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(extract_news_keywords(input.args))),
+        Command {
+            description: "提取新闻关键字到 news_keywords",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "分析服务 TF-IDF 接口地址",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(extract_news_keywords(input.args))),
+        },
+    );
+
+    map.insert(
+        "native_extract_news_keywords".to_string(),
+        Command {
+            description: "本地 TF-IDF 提取新闻关键字到 news_keywords",
+            retryable: true,
+            params: vec![
+                ParamSpec::optional(
+                    "limit",
+                    ParamKind::Integer,
+                    "参与计算的最近新闻条目数，默认 500，最多 5000",
+                ),
+                ParamSpec::optional(
+                    "top_k",
+                    ParamKind::Integer,
+                    "每条新闻保留的关键词数，默认 5，最多 20",
+                ),
+            ],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(native_extract_news_keywords(input.params))
+            }),
+        },
+    );
+
+    map.insert(
+        "native_extract_news_event".to_string(),
+        Command {
+            description: "本地聚类当天新闻条目生成新闻事件",
+            retryable: true,
+            params: vec![
+                ParamSpec::optional(
+                    "event_date",
+                    ParamKind::Date,
+                    "聚类的日期（YYYY-MM-DD），默认当天",
+                ),
+                ParamSpec::optional(
+                    "threshold",
+                    ParamKind::Number,
+                    "两条新闻归为同一事件的最低余弦相似度，默认 0.2",
+                ),
+                ParamSpec::optional(
+                    "min_size",
+                    ParamKind::Integer,
+                    "生成事件所需的最少新闻条数，默认 2",
+                ),
+            ],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(native_extract_news_event(input.params))
+            }),
+        },
     );
 
     map.insert(
         "extract_news_event".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(extract_news_event(input.args))),
+        Command {
+            description: "提取新闻事件",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "分析服务事件提取接口地址",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(extract_news_event(input.args))),
+        },
+    );
+
+    map.insert(
+        "native_merge_news_events".to_string(),
+        Command {
+            description: "本地合并跨天新闻事件",
+            retryable: true,
+            params: vec![
+                ParamSpec::optional(
+                    "event_date",
+                    ParamKind::Date,
+                    "比较窗口的最后一天（YYYY-MM-DD），默认当天",
+                ),
+                ParamSpec::optional(
+                    "days",
+                    ParamKind::Integer,
+                    "向前比较的天数，默认 2，最多 30",
+                ),
+                ParamSpec::optional(
+                    "threshold",
+                    ParamKind::Number,
+                    "合并所需的最低相似度，默认 0.5",
+                ),
+            ],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(native_merge_news_events(input.params))
+            }),
+        },
     );
 
     map.insert(
         "merge_cross_day_news_events".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(merge_cross_day_news_events(input.args))),
+        Command {
+            description: "合并跨天新闻事件",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "分析服务事件合并接口地址",
+            )],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(merge_cross_day_news_events(input.args))
+            }),
+        },
     );
 
     map.insert(
         "fetch_all_news".to_string(),
-        Arc::new(|input: CommandInput| Box::pin(fetch_all_news(input.args))),
+        Command {
+            description: "抓取全量新闻数据",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "新闻源站根地址，如 https://news.likanug.top",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(fetch_all_news(input.args))),
+        },
+    );
+
+    map.insert(
+        "fetch_rss".to_string(),
+        Command {
+            description: "抓取 RSS / Atom 订阅源",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "订阅源地址，支持 RSS 2.0、RSS 1.0 和 Atom",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(fetch_rss(input.args))),
+        },
+    );
+
+    map.insert(
+        "fetch_bilibili_ani".to_string(),
+        Command {
+            description: "抓取 B 站番剧今日更新",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "B 站番剧时间表接口地址，如 https://api.bilibili.com/pgc/web/timeline?types=1&before=1&after=1",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(fetch_bilibili_ani(input.args))),
+        },
+    );
+
+    map.insert(
+        "fetch_iqiyi_ani".to_string(),
+        Command {
+            description: "抓取爱奇艺动漫更新表",
+            retryable: true,
+            params: vec![ParamSpec::required(
+                "arg",
+                ParamKind::Url,
+                "爱奇艺动漫频道更新表接口地址",
+            )],
+            handler: Arc::new(|input: CommandInput| Box::pin(fetch_iqiyi_ani(input.args))),
+        },
+    );
+
+    map.insert(
+        "scrape_html".to_string(),
+        Command {
+            description: "按 CSS 选择器抓取网页新闻列表",
+            retryable: true,
+            params: vec![
+                ParamSpec::required("arg", ParamKind::Url, "列表页地址"),
+                ParamSpec::required("item_selector", ParamKind::Selector, "列表条目选择器"),
+                ParamSpec::required("title_selector", ParamKind::Selector, "条目内标题选择器"),
+                ParamSpec::optional(
+                    "link_selector",
+                    ParamKind::Selector,
+                    "条目内链接选择器，取 href，缺省取第一个链接",
+                ),
+                ParamSpec::optional(
+                    "date_selector",
+                    ParamKind::Selector,
+                    "条目内发布时间选择器，优先取 datetime 属性",
+                ),
+                ParamSpec::optional("image_selector", ParamKind::Selector, "条目内图片选择器"),
+                ParamSpec::optional("source_name", ParamKind::String, "来源名称，缺省取页面标题"),
+            ],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(scrape_html(input.args, input.params))
+            }),
+        },
+    );
+
+    map.insert(
+        "cleanup_expired_refresh_tokens".to_string(),
+        Command {
+            description: "删除已过期的 refresh_token",
+            retryable: true,
+            params: vec![ParamSpec::optional(
+                "retention_days",
+                ParamKind::Integer,
+                "过期后保留的天数，默认 1，最多 365",
+            )],
+            handler: Arc::new(|input: CommandInput| {
+                Box::pin(cleanup_expired_refresh_tokens(input.params))
+            }),
+        },
     );
 
     map
 }
+
+static TASK_DB_POOL: OnceLock<PgPool> = OnceLock::new();
+
+/// 注入数据库连接池，供直接读写数据库的命令使用；只在首次调用时生效
+pub fn init_task_db_pool(pool: PgPool) {
+    let _ = TASK_DB_POOL.set(pool);
+}
+
+pub(crate) fn task_db_pool() -> Result<&'static PgPool, String> {
+    TASK_DB_POOL
+        .get()
+        .ok_or_else(|| "数据库连接池未初始化".to_string())
+}
+
+/// 全局命令表，供接口校验和命令列表查询使用
+pub fn cmd_registry() -> &'static HashMap<String, Command> {
+    static REGISTRY: OnceLock<HashMap<String, Command>> = OnceLock::new();
+    REGISTRY.get_or_init(build_cmd_map)
+}
+
+/// 按 params.cmd 找到命令并校验其余参数
+pub fn validate_cmd_params(params: &Value) -> Result<(), ParamError> {
+    let cmd = params
+        .get("cmd")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or("");
+    if cmd.is_empty() {
+        return Err(ParamError {
+            field: "cmd".to_string(),
+            message: "不能为空".to_string(),
+        });
+    }
+    let command = cmd_registry().get(cmd).ok_or_else(|| ParamError {
+        field: "cmd".to_string(),
+        message: format!("不支持的命令 '{cmd}'"),
+    })?;
+    command.validate_params(params)
+}
+
+/// params.cmd 对应的命令是否支持失败重试；命令不存在时按支持处理，由参数校验报错
+pub fn is_cmd_retryable(params: &Value) -> bool {
+    params
+        .get("cmd")
+        .and_then(Value::as_str)
+        .and_then(|cmd| cmd_registry().get(cmd.trim()))
+        .is_none_or(|command| command.retryable)
+}
+
+/// 不支持重试的命令只能配置 retry_times 为 0
+pub fn validate_cmd_retry_times(params: &Value, retry_times: u8) -> Result<(), String> {
+    if retry_times > 0 && !is_cmd_retryable(params) {
+        let cmd = params
+            .get("cmd")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .trim();
+        return Err(format!("命令 '{cmd}' 不支持失败重试，retryTimes 必须为 0"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParamKind, validate_cmd_params, validate_cmd_retry_times};
+    use serde_json::json;
+
+    fn error_field(params: serde_json::Value) -> String {
+        validate_cmd_params(&params).unwrap_err().field
+    }
+
+    #[test]
+    fn validate_cmd_params_accepts_valid_params() {
+        assert!(
+            validate_cmd_params(&json!({
+                "cmd": "health_check",
+                "arg": "https://a.example.com/health, https://b.example.com/health",
+                "timeout_secs": 30
+            }))
+            .is_ok()
+        );
+        assert!(
+            validate_cmd_params(
+                &json!({"cmd": "fetch_all_news", "arg": "https://news.likanug.top"})
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn validate_cmd_params_reports_offending_field() {
+        assert_eq!(error_field(json!({"arg": "https://a.com"})), "cmd");
+        assert_eq!(error_field(json!({"cmd": "sync_news"})), "cmd");
+        assert_eq!(error_field(json!({"cmd": "health_check"})), "arg");
+        assert_eq!(
+            error_field(json!({"cmd": "extract_news_event", "arg": "not a url"})),
+            "arg"
+        );
+        assert_eq!(
+            error_field(json!({"cmd": "health_check", "arg": "https://a.com,ftp://b.com"})),
+            "arg"
+        );
+    }
+
+    #[test]
+    fn param_kind_checks_value_types() {
+        assert!(ParamKind::Integer.check(&json!(3)).is_ok());
+        assert!(ParamKind::Integer.check(&json!("3")).is_err());
+        assert!(ParamKind::Boolean.check(&json!(true)).is_ok());
+        assert!(ParamKind::String.check(&json!("  ")).is_err());
+        assert!(ParamKind::Url.check(&json!("file:///etc/hosts")).is_err());
+        assert!(ParamKind::Selector.check(&json!("ul > li.item")).is_ok());
+        assert!(ParamKind::Selector.check(&json!("li[")).is_err());
+        assert!(ParamKind::Number.check(&json!(0.35)).is_ok());
+        assert!(ParamKind::Number.check(&json!("0.35")).is_err());
+        assert!(ParamKind::Date.check(&json!("2025-09-16")).is_ok());
+        assert!(ParamKind::Date.check(&json!("2025/09/16")).is_err());
+        assert!(ParamKind::Regex.check(&json!(r"\d+")).is_ok());
+        assert!(ParamKind::Regex.check(&json!("(")).is_err());
+    }
+
+    #[test]
+    fn validate_cmd_retry_times_rejects_retries_for_non_retryable_command() {
+        let health_check = json!({"cmd": "health_check", "arg": "https://a.com"});
+        assert!(validate_cmd_retry_times(&health_check, 0).is_ok());
+        assert_eq!(
+            validate_cmd_retry_times(&health_check, 3).unwrap_err(),
+            "命令 'health_check' 不支持失败重试，retryTimes 必须为 0"
+        );
+        let fetch = json!({"cmd": "fetch_all_news", "arg": "https://news.likanug.top"});
+        assert!(validate_cmd_retry_times(&fetch, 3).is_ok());
+    }
+}
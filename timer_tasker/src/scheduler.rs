@@ -1,46 +1,100 @@
-use crate::task::{Task, TaskResult};
+use crate::task::{ConcurrencyPolicy, Task, TaskResult, TaskSchedule, TaskTrigger};
 use chrono::Utc;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use tokio::sync::{Semaphore, mpsc, watch};
-use tokio::time::{Duration, Instant, sleep};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant, sleep, timeout};
 use tracing::{info, warn};
 
+/// 调度中的单个任务：独立的调度循环和停止信号，可按名称单独增删替换
+struct TaskEntry {
+    task: Arc<Task>,
+    stop_tx: watch::Sender<bool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// 分发触发时共享的调度资源
+#[derive(Clone)]
+struct DispatchContext {
+    /// 全局并发许可
+    semaphore: Arc<Semaphore>,
+    /// 用于在上游成功后查找下游任务
+    entries: Arc<Mutex<HashMap<String, TaskEntry>>>,
+    shutdown: watch::Receiver<bool>,
+}
+
 #[derive(Clone)]
 pub struct Scheduler {
-    pub tasks: Vec<Arc<Task>>,
+    entries: Arc<Mutex<HashMap<String, TaskEntry>>>,
+    /// run 之后才有值，调度器停止时清空，以便结果通道能够关闭
+    sender: Arc<Mutex<Option<mpsc::Sender<TaskResult>>>>,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
     semaphore: Arc<Semaphore>,
 }
 
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 impl Scheduler {
     pub fn new(tasks: Vec<Task>, max_concurrent_tasks: Option<usize>) -> Self {
         let default_max_concurrent_tasks = num_cpus::get();
         let max_concurrent_tasks = max_concurrent_tasks.unwrap_or(default_max_concurrent_tasks);
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
+        let entries = tasks
+            .into_iter()
+            .map(|task| {
+                let (stop_tx, _) = watch::channel(false);
+                let entry = TaskEntry {
+                    task: Arc::new(task),
+                    stop_tx,
+                    handle: None,
+                };
+                (entry.task.name.clone(), entry)
+            })
+            .collect();
+
         Self {
-            tasks: tasks.into_iter().map(Arc::new).collect(),
+            entries: Arc::new(Mutex::new(entries)),
+            sender: Arc::new(Mutex::new(None)),
             shutdown_tx,
             shutdown_rx,
             semaphore: Arc::new(Semaphore::new(max_concurrent_tasks)),
         }
     }
 
+    /// 启动全部任务的调度循环，直到 stop 被调用且各循环退出后返回
     pub async fn run(&self, sender: mpsc::Sender<TaskResult>) {
-        let mut task_handles = Vec::with_capacity(self.tasks.len());
-
-        for task in &self.tasks {
-            let t = task.clone();
-            let s = sender.clone();
-            let shutdown = self.shutdown_rx.clone();
-            let semaphore = self.semaphore.clone();
+        {
+            *lock(&self.sender) = Some(sender.clone());
+            let mut entries = lock(&self.entries);
+            for entry in entries.values_mut() {
+                if entry.handle.is_none() {
+                    entry.handle = Some(self.spawn_task_loop(
+                        entry.task.clone(),
+                        sender.clone(),
+                        entry.stop_tx.subscribe(),
+                    ));
+                }
+            }
+        }
+        drop(sender);
 
-            task_handles.push(tokio::spawn(async move {
-                Self::run_single_task(t, s, semaphore, shutdown).await;
-            }));
+        let mut shutdown = self.shutdown_rx.clone();
+        while !*shutdown.borrow_and_update() {
+            if shutdown.changed().await.is_err() {
+                break;
+            }
         }
 
+        let task_handles: Vec<JoinHandle<()>> = lock(&self.entries)
+            .values_mut()
+            .filter_map(|entry| entry.handle.take())
+            .collect();
         for handle in task_handles {
             if let Err(e) = handle.await {
                 warn!(error = %e, "任务调度线程退出异常");
@@ -48,7 +102,81 @@ impl Scheduler {
         }
     }
 
-    fn next_run_delay(schedule: &cron::Schedule, now: chrono::DateTime<Utc>) -> Option<Duration> {
+    /// 新增或替换同名任务：旧任务的调度循环停止（在途执行不受影响），新任务立即开始调度
+    ///
+    /// 替换时沿用旧任务的运行状态，并发策略对新旧执行同样生效
+    pub fn upsert_task(&self, mut task: Task) {
+        let sender = lock(&self.sender).clone();
+        let mut entries = lock(&self.entries);
+
+        if let Some(old) = entries.remove(&task.name) {
+            old.stop_tx.send_replace(true);
+            task.run_state = old.task.run_state.clone();
+            info!(task_name = %task.name, "替换调度中的任务");
+        } else {
+            info!(task_name = %task.name, "新增调度任务");
+        }
+
+        let task = Arc::new(task);
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let handle = sender.map(|sender| self.spawn_task_loop(task.clone(), sender, stop_rx));
+        entries.insert(
+            task.name.clone(),
+            TaskEntry {
+                task,
+                stop_tx,
+                handle,
+            },
+        );
+    }
+
+    /// 按名称移除任务，只停止其调度循环，在途执行继续完成；任务不存在时返回 false
+    pub fn remove_task(&self, name: &str) -> bool {
+        match lock(&self.entries).remove(name) {
+            Some(entry) => {
+                entry.stop_tx.send_replace(true);
+                info!(task_name = %name, "移除调度任务");
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn task(&self, name: &str) -> Option<Arc<Task>> {
+        lock(&self.entries)
+            .get(name)
+            .map(|entry| entry.task.clone())
+    }
+
+    pub fn task_names(&self) -> Vec<String> {
+        lock(&self.entries).keys().cloned().collect()
+    }
+
+    pub fn task_count(&self) -> usize {
+        lock(&self.entries).len()
+    }
+
+    fn context(&self) -> DispatchContext {
+        DispatchContext {
+            semaphore: self.semaphore.clone(),
+            entries: self.entries.clone(),
+            shutdown: self.shutdown_rx.clone(),
+        }
+    }
+
+    fn spawn_task_loop(
+        &self,
+        task: Arc<Task>,
+        sender: mpsc::Sender<TaskResult>,
+        stop_rx: watch::Receiver<bool>,
+    ) -> JoinHandle<()> {
+        let ctx = self.context();
+        tokio::spawn(async move {
+            Self::run_single_task(task, sender, ctx, stop_rx).await;
+        })
+    }
+
+    fn next_run_delay(schedule: &TaskSchedule, now: chrono::DateTime<Utc>) -> Option<Duration> {
         let next_time = schedule.after(&now).next()?;
         Some((next_time - now).to_std().unwrap_or(Duration::from_secs(0)))
     }
@@ -65,9 +193,14 @@ impl Scheduler {
     async fn run_single_task(
         task: Arc<Task>,
         sender: mpsc::Sender<TaskResult>,
-        semaphore: Arc<Semaphore>,
+        ctx: DispatchContext,
         mut shutdown: watch::Receiver<bool>,
     ) {
+        if task.has_upstream() {
+            info!(task_name = %task.name, depends_on = ?task.depends_on, "任务由上游执行成功触发，不按 cron 调度");
+            return;
+        }
+
         let schedule = match task.schedule() {
             Ok(s) => s,
             Err(e) => {
@@ -89,21 +222,14 @@ impl Scheduler {
 
             tokio::select! {
                 _ = sleep(duration) => {
-                    match semaphore.clone().acquire_owned().await {
-                        Ok(permit) => {
-                            let t = task.clone();
-                            let s = sender.clone();
-                            let schedule_for_exec = schedule.clone();
-                            tokio::spawn(async move {
-                                let _permit = permit;
-                                Self::execute_task(t, s, schedule_for_exec).await;
-                            });
-                        }
-                        Err(e) => {
-                            warn!(task_name = %task.name, error = %e, "信号量已关闭，退出调度");
-                            break;
-                        }
-                    }
+                    tokio::spawn(Self::dispatch(
+                        task.clone(),
+                        sender.clone(),
+                        ctx.clone(),
+                        schedule.clone(),
+                        TaskTrigger::Cron,
+                        None,
+                    ));
                 }
                 change = shutdown.changed() => {
                     match change {
@@ -122,19 +248,254 @@ impl Scheduler {
         }
     }
 
-    async fn execute_task(
+    /// 立即执行一次任务，不受 cron 约束，与定时触发共用并发策略、并发限制和重试流程
+    pub fn run_now(
+        &self,
+        mut task: Task,
+        sender: mpsc::Sender<TaskResult>,
+        run_id: Option<i64>,
+    ) -> Result<(), String> {
+        let schedule = task.schedule()?;
+        // 与调度中的同名任务共享运行状态，使并发策略对手动执行同样生效
+        if let Some(scheduled) = self.task(&task.name) {
+            task.run_state = scheduled.run_state.clone();
+        }
+
+        tokio::spawn(Self::dispatch(
+            Arc::new(task),
+            sender,
+            self.context(),
+            schedule,
+            TaskTrigger::Manual,
+            run_id,
+        ));
+
+        Ok(())
+    }
+
+    /// 补跑错过的触发：在后台按次数依次执行，前一次结束后再开始下一次，
+    /// 与定时触发共用并发策略、并发限制和重试流程
+    pub fn catch_up(
+        &self,
+        name: &str,
+        sender: mpsc::Sender<TaskResult>,
+        runs: u32,
+    ) -> Result<(), String> {
+        let task = self
+            .task(name)
+            .ok_or_else(|| format!("任务 [{name}] 不在调度中"))?;
+        let schedule = task.schedule()?;
+        let ctx = self.context();
+        let shutdown = self.shutdown_rx.clone();
+
+        tokio::spawn(async move {
+            for run in 1..=runs {
+                if *shutdown.borrow() {
+                    warn!(task_name = %task.name, "收到停止信号，停止补跑");
+                    break;
+                }
+                info!(task_name = %task.name, run, runs, "补跑错过的触发");
+                Self::dispatch(
+                    task.clone(),
+                    sender.clone(),
+                    ctx.clone(),
+                    schedule.clone(),
+                    TaskTrigger::Misfire,
+                    None,
+                )
+                .await;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// 按任务的并发策略决定本次触发是执行、排队还是跳过，执行前占用全局并发许可；
+    /// 执行成功后触发下游任务
+    async fn dispatch(
         task: Arc<Task>,
         sender: mpsc::Sender<TaskResult>,
-        schedule: cron::Schedule,
+        ctx: DispatchContext,
+        schedule: TaskSchedule,
+        trigger: TaskTrigger,
+        run_id: Option<i64>,
+    ) {
+        let state = task.run_state.clone();
+        let _slot = match task.concurrency_policy {
+            ConcurrencyPolicy::Allow => None,
+            ConcurrencyPolicy::SkipIfRunning => match state.slot.clone().try_acquire_owned() {
+                Ok(slot) => Some(slot),
+                Err(_) => {
+                    Self::report_skipped(&task, &sender, &schedule, trigger, run_id).await;
+                    return;
+                }
+            },
+            ConcurrencyPolicy::QueueOne => match state.slot.clone().try_acquire_owned() {
+                Ok(slot) => Some(slot),
+                Err(_) if state.queued.swap(true, Ordering::SeqCst) => {
+                    Self::report_skipped(&task, &sender, &schedule, trigger, run_id).await;
+                    return;
+                }
+                Err(_) => {
+                    info!(task_name = %task.name, "上一次执行尚未结束，本次触发进入排队");
+                    let slot = state.slot.clone().acquire_owned().await;
+                    state.queued.store(false, Ordering::SeqCst);
+                    match slot {
+                        Ok(slot) => Some(slot),
+                        Err(e) => {
+                            warn!(task_name = %task.name, error = %e, "任务槽位已关闭，取消排队");
+                            return;
+                        }
+                    }
+                }
+            },
+        };
+
+        let succeeded = match ctx.semaphore.clone().acquire_owned().await {
+            Ok(permit) => {
+                let _permit = permit;
+                Self::execute_task(task.clone(), sender.clone(), schedule, trigger, run_id).await
+            }
+            Err(e) => {
+                warn!(task_name = %task.name, error = %e, "信号量已关闭，取消执行");
+                false
+            }
+        };
+        drop(_slot);
+
+        if succeeded {
+            Self::trigger_downstream(&task, &sender, &ctx);
+        }
+    }
+
+    /// 记录上游执行成功，触发 depends_on 中调度中的上游均已成功的下游任务
+    ///
+    /// 下游触发后清空其记录，因此多个上游（菱形依赖）每轮只触发下游一次，
+    /// 同一上游在下游触发前多次成功也只计一次；未在调度中的上游不参与等待
+    fn trigger_downstream(
+        upstream: &Task,
+        sender: &mpsc::Sender<TaskResult>,
+        ctx: &DispatchContext,
+    ) {
+        if *ctx.shutdown.borrow() {
+            return;
+        }
+        let downstream: Vec<Arc<Task>> = {
+            let entries = lock(&ctx.entries);
+            entries
+                .values()
+                .filter(|entry| entry.task.depends_on.contains(&upstream.name))
+                .filter(|entry| {
+                    let task = &entry.task;
+                    let mut succeeded = lock(&task.run_state.succeeded_upstreams);
+                    succeeded.insert(upstream.name.clone());
+                    let ready = task
+                        .depends_on
+                        .iter()
+                        .filter(|name| entries.contains_key(*name))
+                        .all(|name| succeeded.contains(name));
+                    if ready {
+                        succeeded.clear();
+                    } else {
+                        info!(task_name = %task.name, upstream = %upstream.name, "上游任务执行成功，等待其余上游");
+                    }
+                    ready
+                })
+                .map(|entry| entry.task.clone())
+                .collect()
+        };
+
+        for task in downstream {
+            let schedule = match task.schedule() {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!(task_name = %task.name, error = %e, "cron 表达式无效，跳过下游触发");
+                    continue;
+                }
+            };
+            info!(task_name = %task.name, upstream = %upstream.name, "上游任务执行成功，触发下游任务");
+            tokio::spawn(Self::dispatch(
+                task,
+                sender.clone(),
+                ctx.clone(),
+                schedule,
+                TaskTrigger::Dependency,
+                None,
+            ));
+        }
+    }
+
+    /// 有上游的任务不按 cron 调度，没有下次运行时间
+    fn next_run_of(task: &Task, schedule: &TaskSchedule) -> Option<chrono::DateTime<Utc>> {
+        if task.has_upstream() {
+            None
+        } else {
+            schedule.next_run()
+        }
+    }
+
+    async fn report_skipped(
+        task: &Task,
+        sender: &mpsc::Sender<TaskResult>,
+        schedule: &TaskSchedule,
+        trigger: TaskTrigger,
+        run_id: Option<i64>,
     ) {
+        warn!(
+            task_name = %task.name,
+            policy = task.concurrency_policy.as_str(),
+            "上一次执行尚未结束，跳过本次触发"
+        );
+        let now = Utc::now();
+        let result = TaskResult {
+            name: task.name.clone(),
+            result: None,
+            last_run: now,
+            next_run: Self::next_run_of(task, schedule),
+            last_status: "skipped".to_string(),
+            trigger,
+            run_id,
+            finished_at: now,
+            attempts: 0,
+            error: None,
+            backoff_ms: 0,
+            message: Some(format!(
+                "上一次执行尚未结束，按 {} 策略跳过本次触发",
+                task.concurrency_policy.as_str()
+            )),
+        };
+        if let Err(e) = sender.send(result).await {
+            warn!(task_name = %task.name, error = %e, "任务跳过结果发送失败");
+        }
+    }
+
+    async fn execute_task(
+        task: Arc<Task>,
+        sender: mpsc::Sender<TaskResult>,
+        schedule: TaskSchedule,
+        trigger: TaskTrigger,
+        run_id: Option<i64>,
+    ) -> bool {
         let started_at = Instant::now();
         let last_run = Utc::now();
-        let next_run = schedule.upcoming(Utc).next();
-        let max_attempts = task.retry_times + 1;
+        let next_run = Self::next_run_of(&task, &schedule);
+        let max_attempts = u32::from(task.retry_times) + 1;
+        let mut errors: Vec<String> = Vec::new();
+        let mut backoff_total = Duration::ZERO;
+
+        let mut timed_out = false;
 
         for attempt in 0..=task.retry_times {
-            let current_try = attempt + 1;
-            match task.action.run().await {
+            let current_try = u32::from(attempt) + 1;
+            let (outcome, attempt_timed_out) = match task.timeout_secs {
+                Some(secs) => match timeout(Duration::from_secs(secs), task.action.run()).await {
+                    Ok(outcome) => (outcome, false),
+                    Err(_) => (Err(format!("执行超时（超过 {secs} 秒）")), true),
+                },
+                None => (task.action.run().await, false),
+            };
+            timed_out = attempt_timed_out;
+            match outcome {
                 Ok(resp) => {
                     info!(
                         task_name = %task.name,
@@ -149,13 +510,21 @@ impl Scheduler {
                         last_run,
                         next_run,
                         last_status: "success".to_string(),
+                        trigger,
+                        run_id,
+                        finished_at: Utc::now(),
+                        attempts: current_try,
+                        error: (!errors.is_empty()).then(|| errors.join("\n")),
+                        backoff_ms: backoff_total.as_millis() as u64,
+                        message: resp.message,
                     };
                     if let Err(e) = sender.send(result).await {
                         warn!(task_name = %task.name, error = %e, "任务结果发送失败");
                     }
-                    return;
+                    return true;
                 }
                 Err(e) => {
+                    errors.push(format!("[{current_try}/{max_attempts}] {e}"));
                     if attempt < task.retry_times {
                         let backoff = Self::backoff_with_jitter(attempt);
                         warn!(
@@ -167,6 +536,7 @@ impl Scheduler {
                             elapsed_ms = started_at.elapsed().as_millis() as u64,
                             "任务执行失败，准备重试"
                         );
+                        backoff_total += backoff;
                         sleep(backoff).await;
                     } else {
                         warn!(
@@ -187,23 +557,36 @@ impl Scheduler {
             result: None,
             last_run,
             next_run,
-            last_status: "failed".to_string(),
+            last_status: if timed_out { "timeout" } else { "failed" }.to_string(),
+            trigger,
+            run_id,
+            finished_at: Utc::now(),
+            attempts: max_attempts,
+            error: Some(errors.join("\n")),
+            backoff_ms: backoff_total.as_millis() as u64,
+            message: None,
         };
         if let Err(e) = sender.send(result).await {
             warn!(task_name = %task.name, error = %e, "任务失败结果发送失败");
         }
+        false
     }
 
     pub fn stop(&self) {
-        let _ = self.shutdown_tx.send(true);
+        self.shutdown_tx.send_replace(true);
+        for entry in lock(&self.entries).values() {
+            entry.stop_tx.send_replace(true);
+        }
+        lock(&self.sender).take();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::task::{Task, TaskAction, TaskResult};
+    use crate::task::{ConcurrencyPolicy, Task, TaskAction, TaskResult, TaskTrigger};
     use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+    use chrono_tz::Tz;
     use common::api::ApiResponse;
     use common::po::{ItemResult, NewsInfo, TaskItem};
     use std::collections::HashSet;
@@ -289,7 +672,10 @@ mod tests {
 
     #[test]
     fn test_next_run_delay_uses_utc_baseline() {
-        let schedule = cron::Schedule::from_str("0 */5 * * * * *").unwrap();
+        let schedule = TaskSchedule::new(
+            cron::Schedule::from_str("0 */5 * * * * *").unwrap(),
+            Tz::UTC,
+        );
         let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 2).unwrap();
 
         let delay = Scheduler::next_run_delay(&schedule, now).unwrap();
@@ -297,6 +683,20 @@ mod tests {
         assert_eq!(delay, ChronoDuration::seconds(298).to_std().unwrap());
     }
 
+    #[test]
+    fn test_next_run_delay_follows_task_timezone() {
+        // 北京时间每天 08:00 触发，UTC 23:00 时距下一次（UTC 00:00）还有 1 小时
+        let schedule = TaskSchedule::new(
+            cron::Schedule::from_str("0 0 8 * * * *").unwrap(),
+            Tz::Asia__Shanghai,
+        );
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 23, 0, 0).unwrap();
+
+        let delay = Scheduler::next_run_delay(&schedule, now).unwrap();
+
+        assert_eq!(delay, ChronoDuration::hours(1).to_std().unwrap());
+    }
+
     #[test]
     fn test_backoff_with_jitter_has_bounds() {
         for attempt in 0..=6 {
@@ -312,7 +712,12 @@ mod tests {
         let task = Arc::new(Task {
             name: "flaky-success".into(),
             cron_expr: "*/5 * * * * * *".into(),
+            timezone: Tz::UTC,
             retry_times: 2,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
             action: Arc::new(FlakyAction {
                 counter: attempts.clone(),
                 succeed_on: 3,
@@ -321,11 +726,18 @@ mod tests {
 
         let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
         let schedule = task.schedule().unwrap();
-        Scheduler::execute_task(task, tx, schedule).await;
+        Scheduler::execute_task(task, tx, schedule, TaskTrigger::Cron, None).await;
 
         let result = rx.recv().await.unwrap();
         assert_eq!(result.last_status, "success");
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.trigger, TaskTrigger::Cron);
+        assert!(result.backoff_ms > 0);
+        assert!(result.finished_at >= result.last_run);
+        let error = result.error.unwrap();
+        assert!(error.contains("[1/3] attempt 1 failed"));
+        assert!(error.contains("[2/3] attempt 2 failed"));
     }
 
     #[tokio::test]
@@ -334,7 +746,12 @@ mod tests {
         let task = Arc::new(Task {
             name: "always-fail".into(),
             cron_expr: "*/5 * * * * * *".into(),
+            timezone: Tz::UTC,
             retry_times: 2,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
             action: Arc::new(AlwaysFailAction {
                 counter: attempts.clone(),
             }),
@@ -342,12 +759,285 @@ mod tests {
 
         let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
         let schedule = task.schedule().unwrap();
-        Scheduler::execute_task(task, tx, schedule).await;
+        Scheduler::execute_task(task, tx, schedule, TaskTrigger::Cron, None).await;
 
         let result = rx.recv().await.unwrap();
         assert_eq!(result.last_status, "failed");
         assert!(result.result.is_none());
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.item_count(), 0);
+        assert_eq!(result.error.unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_now_executes_immediately_with_run_id() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let task = Task {
+            name: "manual".into(),
+            // 一年才触发一次，确保结果来自手动执行
+            cron_expr: "0 0 0 1 1 * *".into(),
+            timezone: Tz::UTC,
+            retry_times: 0,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
+            action: Arc::new(FlakyAction {
+                counter: attempts.clone(),
+                succeed_on: 1,
+            }),
+        };
+        let scheduler = Scheduler::new(vec![], Some(1));
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler.run_now(task, tx, Some(7)).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.last_status, "success");
+        assert_eq!(result.trigger, TaskTrigger::Manual);
+        assert_eq!(result.run_id, Some(7));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    struct SlowAction {
+        counter: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl TaskAction for SlowAction {
+        async fn run(&self) -> Result<ApiResponse<ItemResult>, String> {
+            self.counter.fetch_add(1, Ordering::SeqCst);
+            sleep(self.delay).await;
+            Ok(ApiResponse {
+                status: "".to_string(),
+                data: Some(Default::default()),
+                message: None,
+            })
+        }
+    }
+
+    fn slow_task(
+        policy: ConcurrencyPolicy,
+        timeout_secs: Option<u64>,
+        delay: Duration,
+    ) -> (Arc<Task>, Arc<AtomicUsize>) {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let task = Arc::new(Task {
+            name: "slow".into(),
+            cron_expr: "0 0 0 1 1 * *".into(),
+            timezone: Tz::UTC,
+            retry_times: 0,
+            timeout_secs,
+            concurrency_policy: policy,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
+            action: Arc::new(SlowAction {
+                counter: counter.clone(),
+                delay,
+            }),
+        });
+        (task, counter)
+    }
+
+    /// 同时触发 times 次，按完成顺序收集各次的 last_status
+    async fn dispatch_concurrently(task: Arc<Task>, times: usize) -> Vec<String> {
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        let ctx = Scheduler::new(vec![], Some(4)).context();
+        let schedule = task.schedule().unwrap();
+        let mut handles = vec![];
+        for _ in 0..times {
+            handles.push(tokio::spawn(Scheduler::dispatch(
+                task.clone(),
+                tx.clone(),
+                ctx.clone(),
+                schedule.clone(),
+                TaskTrigger::Cron,
+                None,
+            )));
+            // 保证触发顺序确定
+            sleep(Duration::from_millis(20)).await;
+        }
+        drop(tx);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let mut statuses = vec![];
+        while let Some(result) = rx.recv().await {
+            statuses.push(result.last_status);
+        }
+        statuses
+    }
+
+    #[tokio::test]
+    async fn test_execute_task_reports_timeout() {
+        let (task, counter) = slow_task(ConcurrencyPolicy::Allow, Some(1), Duration::from_secs(5));
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        let schedule = task.schedule().unwrap();
+        Scheduler::execute_task(task, tx, schedule, TaskTrigger::Cron, None).await;
+
+        let result = rx.recv().await.unwrap();
+        assert_eq!(result.last_status, "timeout");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert!(result.error.unwrap().contains("执行超时"));
+    }
+
+    #[tokio::test]
+    async fn test_skip_if_running_skips_overlapping_trigger() {
+        let (task, counter) = slow_task(
+            ConcurrencyPolicy::SkipIfRunning,
+            None,
+            Duration::from_millis(300),
+        );
+
+        let statuses = dispatch_concurrently(task, 2).await;
+
+        assert_eq!(statuses, vec!["skipped", "success"]);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_queue_one_runs_one_pending_trigger_and_skips_the_rest() {
+        let (task, counter) = slow_task(
+            ConcurrencyPolicy::QueueOne,
+            None,
+            Duration::from_millis(300),
+        );
+
+        let statuses = dispatch_concurrently(task, 3).await;
+
+        assert_eq!(statuses, vec!["skipped", "success", "success"]);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_allow_runs_overlapping_triggers_in_parallel() {
+        let (task, counter) = slow_task(ConcurrencyPolicy::Allow, None, Duration::from_millis(300));
+
+        let statuses = dispatch_concurrently(task, 2).await;
+
+        assert_eq!(statuses, vec!["success", "success"]);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_runs_missed_triggers_sequentially() {
+        let (task, counter) = slow_task(
+            ConcurrencyPolicy::SkipIfRunning,
+            None,
+            Duration::from_millis(50),
+        );
+        let scheduler = Scheduler::new(vec![Task::clone(&task)], Some(2));
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler.catch_up("slow", tx, 3).unwrap();
+
+        for _ in 0..3 {
+            let result = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(result.trigger, TaskTrigger::Misfire);
+            // 顺序补跑不会被 skip_if_running 策略跳过
+            assert_eq!(result.last_status, "success");
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert!(
+            scheduler
+                .catch_up("missing", mpsc::channel(1).0, 1)
+                .is_err()
+        );
+    }
+
+    fn counting_task(name: &str, counter: Arc<AtomicUsize>) -> Task {
+        Task {
+            name: name.into(),
+            cron_expr: "* * * * * * *".into(),
+            timezone: Tz::UTC,
+            retry_times: 0,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
+            action: Arc::new(FlakyAction {
+                counter,
+                succeed_on: 1,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tasks_can_be_added_replaced_and_removed_while_running() {
+        let counter_a = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(vec![counting_task("A", counter_a.clone())], Some(4));
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(100);
+        let run_handle = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.run(tx).await }
+        });
+        tokio::spawn(async move { while rx.recv().await.is_some() {} });
+
+        sleep(Duration::from_millis(1500)).await;
+        let counter_b = Arc::new(AtomicUsize::new(0));
+        scheduler.upsert_task(counting_task("B", counter_b.clone()));
+        assert!(scheduler.remove_task("A"));
+        assert!(!scheduler.remove_task("A"));
+        let a_after_removal = counter_a.load(Ordering::SeqCst);
+
+        sleep(Duration::from_millis(2100)).await;
+        assert!(a_after_removal >= 1);
+        assert_eq!(counter_a.load(Ordering::SeqCst), a_after_removal);
+        assert!(counter_b.load(Ordering::SeqCst) >= 2);
+
+        // 替换 B：旧 action 不再被调用，新 action 接管
+        let counter_b2 = Arc::new(AtomicUsize::new(0));
+        scheduler.upsert_task(counting_task("B", counter_b2.clone()));
+        let b_after_replace = counter_b.load(Ordering::SeqCst);
+        sleep(Duration::from_millis(2100)).await;
+        assert_eq!(counter_b.load(Ordering::SeqCst), b_after_replace);
+        assert!(counter_b2.load(Ordering::SeqCst) >= 2);
+        assert_eq!(scheduler.task_names(), vec!["B".to_string()]);
+
+        scheduler.stop();
+        tokio::time::timeout(Duration::from_secs(2), run_handle)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_removing_task_keeps_in_flight_execution() {
+        let (slow, counter) =
+            slow_task(ConcurrencyPolicy::Allow, None, Duration::from_millis(1500));
+        let mut task = Task::clone(&slow);
+        task.cron_expr = "* * * * * * *".into();
+        let scheduler = Scheduler::new(vec![task], Some(2));
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        let run_handle = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.run(tx).await }
+        });
+
+        // 等待首次执行开始后移除任务
+        while counter.load(Ordering::SeqCst) == 0 {
+            sleep(Duration::from_millis(50)).await;
+        }
+        assert!(scheduler.remove_task("slow"));
+
+        let result = tokio::time::timeout(Duration::from_secs(3), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.last_status, "success");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        scheduler.stop();
+        run_handle.await.unwrap();
     }
 
     #[tokio::test]
@@ -356,7 +1046,12 @@ mod tests {
         let task_a = Task {
             name: "任务A".into(),
             cron_expr: "*/5 * * * * * *".into(),
+            timezone: Tz::UTC,
             retry_times: 0,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
             action: Arc::new(MockAction {
                 id: "A".into(),
                 counter: counter_a.clone(),
@@ -367,7 +1062,12 @@ mod tests {
         let task_b = Task {
             name: "任务B".into(),
             cron_expr: "*/7 * * * * * *".into(),
+            timezone: Tz::UTC,
             retry_times: 0,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: Vec::new(),
+            run_state: Default::default(),
             action: Arc::new(MockAction {
                 id: "B".into(),
                 counter: counter_b.clone(),
@@ -403,4 +1103,187 @@ mod tests {
         assert!(counter_a.load(Ordering::SeqCst) >= 2);
         assert!(counter_b.load(Ordering::SeqCst) >= 2);
     }
+
+    fn dependent_task(name: &str, upstream: &str, counter: Arc<AtomicUsize>) -> Task {
+        Task {
+            depends_on: vec![upstream.to_string()],
+            ..counting_task(name, counter)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_upstream_triggers_downstream_chain() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let extracted = Arc::new(AtomicUsize::new(0));
+        let merged = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(
+            vec![
+                dependent_task("extract", "fetch", extracted.clone()),
+                dependent_task("merge", "extract", merged.clone()),
+            ],
+            Some(2),
+        );
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler
+            .run_now(counting_task("fetch", fetched.clone()), tx, None)
+            .unwrap();
+
+        let mut results = vec![];
+        for _ in 0..3 {
+            let result = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            results.push((result.name, result.trigger, result.next_run));
+        }
+
+        assert_eq!(
+            results,
+            vec![
+                ("fetch".to_string(), TaskTrigger::Manual, results[0].2),
+                ("extract".to_string(), TaskTrigger::Dependency, None),
+                ("merge".to_string(), TaskTrigger::Dependency, None),
+            ]
+        );
+        assert_eq!(fetched.load(Ordering::SeqCst), 1);
+        assert_eq!(extracted.load(Ordering::SeqCst), 1);
+        assert_eq!(merged.load(Ordering::SeqCst), 1);
+    }
+
+    fn join_task(name: &str, upstreams: &[&str], counter: Arc<AtomicUsize>) -> Task {
+        Task {
+            depends_on: upstreams.iter().map(|u| u.to_string()).collect(),
+            ..counting_task(name, counter)
+        }
+    }
+
+    /// 收集结果直到全部执行结束（发送端全部释放）
+    async fn collect_names(rx: &mut mpsc::Receiver<TaskResult>) -> Vec<String> {
+        let mut names = vec![];
+        while let Some(result) = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .unwrap()
+        {
+            names.push(result.name);
+        }
+        names
+    }
+
+    #[tokio::test]
+    async fn test_two_parent_downstream_runs_once_after_both_upstreams() {
+        let left = Arc::new(AtomicUsize::new(0));
+        let right = Arc::new(AtomicUsize::new(0));
+        let joined = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(
+            vec![
+                dependent_task("left", "fetch", left.clone()),
+                dependent_task("right", "fetch", right.clone()),
+                join_task("join", &["left", "right"], joined.clone()),
+            ],
+            Some(4),
+        );
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler
+            .run_now(
+                counting_task("fetch", Arc::new(AtomicUsize::new(0))),
+                tx,
+                None,
+            )
+            .unwrap();
+
+        let names = collect_names(&mut rx).await;
+        assert_eq!(names.len(), 4);
+        assert_eq!(names.iter().filter(|n| *n == "join").count(), 1);
+        assert_eq!(names.last().map(String::as_str), Some("join"));
+        assert_eq!(left.load(Ordering::SeqCst), 1);
+        assert_eq!(right.load(Ordering::SeqCst), 1);
+        assert_eq!(joined.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_downstream_waits_for_every_upstream() {
+        let joined = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(
+            vec![
+                counting_task("left", Arc::new(AtomicUsize::new(0))),
+                counting_task("right", Arc::new(AtomicUsize::new(0))),
+                join_task("join", &["left", "right"], joined.clone()),
+            ],
+            Some(4),
+        );
+
+        // 同一上游重复成功不会凑齐依赖
+        for _ in 0..2 {
+            let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+            scheduler
+                .run_now(
+                    counting_task("left", Arc::new(AtomicUsize::new(0))),
+                    tx,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(collect_names(&mut rx).await, vec!["left".to_string()]);
+        }
+        assert_eq!(joined.load(Ordering::SeqCst), 0);
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler
+            .run_now(
+                counting_task("right", Arc::new(AtomicUsize::new(0))),
+                tx,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            collect_names(&mut rx).await,
+            vec!["right".to_string(), "join".to_string()]
+        );
+        assert_eq!(joined.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_upstream_does_not_trigger_downstream() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let downstream = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(
+            vec![dependent_task("extract", "fetch", downstream.clone())],
+            Some(2),
+        );
+        let upstream = Task {
+            action: Arc::new(AlwaysFailAction {
+                counter: attempts.clone(),
+            }),
+            ..counting_task("fetch", Arc::new(AtomicUsize::new(0)))
+        };
+
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(8);
+        scheduler.run_now(upstream, tx, None).unwrap();
+
+        let result = rx.recv().await.unwrap();
+        assert_eq!(result.last_status, "failed");
+        assert!(rx.recv().await.is_none());
+        assert_eq!(downstream.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_downstream_task_is_not_cron_scheduled() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new(
+            vec![dependent_task("extract", "fetch", counter.clone())],
+            Some(2),
+        );
+
+        let (tx, _rx) = mpsc::channel::<TaskResult>(8);
+        let handle = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.run(tx).await }
+        });
+        sleep(Duration::from_millis(1_500)).await;
+        scheduler.stop();
+        handle.await.unwrap();
+
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+    }
 }
@@ -1,28 +1,251 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use common::api::ApiResponse;
 use common::po::ItemResult;
 use cron::Schedule;
 use serde::Deserialize;
-use service::timer_task_command::CmdFn;
+use serde_json::Value;
+use service::timer_task_command::Command;
 use service::timer_task_command::CommandInput;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::str::FromStr;
-use std::sync::Arc;
-use tracing::warn;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tracing::{error, warn};
 
 /// -----------------
 /// 配置层 TaskMeta
 /// -----------------
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 pub struct TaskMeta {
     pub name: String,
     pub cmd: String,
     pub url: String,
     pub arg: String,
     pub cron_expr: String,
+    /// cron 表达式所在时区，缺省为 UTC
+    #[serde(default)]
+    pub timezone: Tz,
     pub retry_times: u8,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// 上游任务名称，全部上游执行成功后触发本任务
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// 任务完整的 params，供需要多个参数的命令读取
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// 同一任务上一次执行尚未结束时，对新触发的处理策略
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrencyPolicy {
+    /// 允许并行执行
+    #[default]
+    Allow,
+    /// 跳过本次触发
+    SkipIfRunning,
+    /// 最多排队一次，上一次结束后立即执行，排队已满时跳过
+    QueueOne,
+}
+
+impl ConcurrencyPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConcurrencyPolicy::Allow => "allow",
+            ConcurrencyPolicy::SkipIfRunning => "skip_if_running",
+            ConcurrencyPolicy::QueueOne => "queue_one",
+        }
+    }
+}
+
+impl FromStr for ConcurrencyPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(ConcurrencyPolicy::Allow),
+            "skip_if_running" => Ok(ConcurrencyPolicy::SkipIfRunning),
+            "queue_one" => Ok(ConcurrencyPolicy::QueueOne),
+            other => Err(format!(
+                "concurrency_policy 不支持 '{other}'，可选值：allow、skip_if_running、queue_one"
+            )),
+        }
+    }
+}
+
+/// 服务停机等原因错过触发时间（misfire）后的补跑策略
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MisfirePolicy {
+    /// 不补跑，等待下一次触发
+    #[default]
+    Ignore,
+    /// 无论错过几次，只补跑一次
+    RunOnce,
+    /// 错过几次补跑几次，受 misfire_max_runs 上限约束
+    RunAllMissed,
+}
+
+impl MisfirePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MisfirePolicy::Ignore => "ignore",
+            MisfirePolicy::RunOnce => "run_once",
+            MisfirePolicy::RunAllMissed => "run_all_missed",
+        }
+    }
+
+    /// 计算需要补跑的次数
+    ///
+    /// `missed_from` 为持久化的下次运行时间，早于 `now` 表示该时间点及之后 `now` 之前的触发均已错过
+    pub fn missed_runs(
+        &self,
+        schedule: &TaskSchedule,
+        missed_from: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_runs: u32,
+    ) -> u32 {
+        if missed_from >= now {
+            return 0;
+        }
+        match self {
+            MisfirePolicy::Ignore => 0,
+            MisfirePolicy::RunOnce => 1,
+            MisfirePolicy::RunAllMissed => {
+                let later = schedule
+                    .after(&missed_from)
+                    .take_while(|t| *t < now)
+                    .take(max_runs as usize)
+                    .count() as u32;
+                (1 + later).min(max_runs)
+            }
+        }
+    }
+}
+
+impl FromStr for MisfirePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(MisfirePolicy::Ignore),
+            "run_once" => Ok(MisfirePolicy::RunOnce),
+            "run_all_missed" => Ok(MisfirePolicy::RunAllMissed),
+            other => Err(format!(
+                "misfire_policy 不支持 '{other}'，可选值：ignore、run_once、run_all_missed"
+            )),
+        }
+    }
+}
+
+/// run_all_missed 策略默认的最多补跑次数
+pub const DEFAULT_MISFIRE_MAX_RUNS: u32 = 10;
+
+/// scheduled_tasks.params 中与执行控制相关的配置
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionOptions {
+    /// 单次尝试的超时时间（秒），未配置时不限制
+    pub timeout_secs: Option<u64>,
+    pub concurrency_policy: ConcurrencyPolicy,
+    pub misfire_policy: MisfirePolicy,
+    /// run_all_missed 策略最多补跑次数
+    pub misfire_max_runs: u32,
+    /// 上游任务名称；声明后任务在全部上游执行成功后触发，不再按 cron 调度
+    pub depends_on: Vec<String>,
+}
+
+impl Default for ExecutionOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::default(),
+            misfire_policy: MisfirePolicy::default(),
+            misfire_max_runs: DEFAULT_MISFIRE_MAX_RUNS,
+            depends_on: Vec::new(),
+        }
+    }
+}
+
+impl ExecutionOptions {
+    /// 从任务 params 解析执行控制字段，字段缺失时取默认值
+    pub fn from_params(params: &Value) -> Result<Self, String> {
+        let timeout_secs = match params.get("timeout_secs") {
+            None | Some(Value::Null) => None,
+            Some(v) => match v.as_u64() {
+                Some(secs) if secs > 0 => Some(secs),
+                _ => return Err("timeout_secs 必须为正整数".to_string()),
+            },
+        };
+        let concurrency_policy = match params.get("concurrency_policy") {
+            None | Some(Value::Null) => ConcurrencyPolicy::default(),
+            Some(Value::String(s)) => s.parse()?,
+            Some(_) => return Err("concurrency_policy 必须为字符串".to_string()),
+        };
+        let misfire_policy = match params.get("misfire_policy") {
+            None | Some(Value::Null) => MisfirePolicy::default(),
+            Some(Value::String(s)) => s.parse()?,
+            Some(_) => return Err("misfire_policy 必须为字符串".to_string()),
+        };
+        let misfire_max_runs = match params.get("misfire_max_runs") {
+            None | Some(Value::Null) => DEFAULT_MISFIRE_MAX_RUNS,
+            Some(v) => match v.as_u64().and_then(|n| u32::try_from(n).ok()) {
+                Some(n) if n > 0 => n,
+                _ => return Err("misfire_max_runs 必须为正整数".to_string()),
+            },
+        };
+        let depends_on = match params.get("depends_on") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(items)) => {
+                let mut depends_on: Vec<String> = Vec::with_capacity(items.len());
+                for item in items {
+                    match item.as_str().map(str::trim) {
+                        Some(name) if !name.is_empty() => {
+                            if !depends_on.iter().any(|n| n == name) {
+                                depends_on.push(name.to_string());
+                            }
+                        }
+                        _ => return Err("depends_on 必须为非空任务名称组成的数组".to_string()),
+                    }
+                }
+                depends_on
+            }
+            Some(_) => return Err("depends_on 必须为任务名称数组".to_string()),
+        };
+        Ok(Self {
+            timeout_secs,
+            concurrency_policy,
+            misfire_policy,
+            misfire_max_runs,
+            depends_on,
+        })
+    }
+}
+
+/// 任务运行期共享状态，供并发策略判断是否有执行在途
+#[derive(Debug)]
+pub(crate) struct TaskRunState {
+    /// 在途执行占用的槽位
+    pub(crate) slot: Arc<Semaphore>,
+    /// 是否已有一次触发在排队
+    pub(crate) queued: AtomicBool,
+    /// 本任务上次由依赖触发之后，已执行成功的上游任务
+    pub(crate) succeeded_upstreams: Mutex<HashSet<String>>,
+}
+
+impl Default for TaskRunState {
+    fn default() -> Self {
+        Self {
+            slot: Arc::new(Semaphore::new(1)),
+            queued: AtomicBool::new(false),
+            succeeded_upstreams: Mutex::default(),
+        }
+    }
 }
 
 /// -----------------
@@ -51,8 +274,13 @@ where
 pub struct Task {
     pub name: String,
     pub cron_expr: String,
+    pub timezone: Tz,
     pub action: Arc<dyn TaskAction>,
     pub retry_times: u8,
+    pub timeout_secs: Option<u64>,
+    pub concurrency_policy: ConcurrencyPolicy,
+    pub depends_on: Vec<String>,
+    pub(crate) run_state: Arc<TaskRunState>,
 }
 
 impl Task {
@@ -64,39 +292,170 @@ impl Task {
         Self {
             name: meta.name.clone(),
             cron_expr: meta.cron_expr.clone(),
+            timezone: meta.timezone,
             action: Arc::new(action),
             retry_times: meta.retry_times,
+            timeout_secs: meta.timeout_secs,
+            concurrency_policy: meta.concurrency_policy,
+            depends_on: meta.depends_on.clone(),
+            run_state: Arc::default(),
         }
     }
 
-    pub fn schedule(&self) -> Result<Schedule, String> {
-        Schedule::from_str(&self.cron_expr).map_err(|e| {
+    /// 是否声明了上游任务；有上游的任务只由上游执行成功触发，不按 cron 调度
+    pub fn has_upstream(&self) -> bool {
+        !self.depends_on.is_empty()
+    }
+
+    /// 解析 cron 表达式，按任务时区计算触发时间
+    pub fn schedule(&self) -> Result<TaskSchedule, String> {
+        let schedule =
+            parse_cron(&self.cron_expr).map_err(|e| format!("任务 [{}] {e}", self.name))?;
+        Ok(TaskSchedule::new(schedule, self.timezone))
+    }
+}
+
+/// 绑定时区的 cron 调度：各字段按任务时区的本地时间解释，返回的触发时间统一为 UTC
+#[derive(Clone, Debug)]
+pub struct TaskSchedule {
+    schedule: Schedule,
+    timezone: Tz,
+}
+
+impl TaskSchedule {
+    pub fn new(schedule: Schedule, timezone: Tz) -> Self {
+        Self { schedule, timezone }
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// `after` 之后（不含）的触发时间序列
+    pub fn after(&self, after: &DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.schedule
+            .after(&after.with_timezone(&self.timezone))
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// 当前时间之后的下一次触发时间
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.after(&Utc::now()).next()
+    }
+}
+
+/// 解析 cron 表达式：6 段或 7 段，依次为 秒 分 时 日 月 周 [年]
+///
+/// 常见的 5 段 Unix 写法会被拒绝并提示补上秒，避免误存成每秒触发之类的表达式
+pub fn parse_cron(expr: &str) -> Result<Schedule, String> {
+    let expr = expr.trim();
+    if expr.is_empty() {
+        return Err("cron 不能为空".to_string());
+    }
+    Schedule::from_str(expr).map_err(|e| {
+        if expr.split_whitespace().count() == 5 {
             format!(
-                "任务 [{}] cron 表达式无效 '{}': {e}",
-                self.name, self.cron_expr
+                "cron 表达式不合法 '{expr}'：需为 6 或 7 段（秒 分 时 日 月 周 [年]），\
+                 当前为 5 段的 Unix 写法，可在开头补上秒，如 '0 {expr}'"
             )
+        } else {
+            format!("cron 表达式不合法 '{expr}'（格式：秒 分 时 日 月 周 [年]）: {e}")
+        }
+    })
+}
+
+/// 校验并解析 IANA 时区名，例如 `Asia/Shanghai`、`UTC`
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("timezone 不支持 '{name}'，需为 IANA 时区名，如 Asia/Shanghai"))
+}
+
+/// 找出 depends_on 构成环的任务，每个环内的任务名按字典序排列
+///
+/// 只考虑 metas 内部的依赖，指向 metas 之外任务的依赖忽略
+pub fn find_dependency_cycles(metas: &[TaskMeta]) -> Vec<Vec<String>> {
+    let index: HashMap<&str, usize> = metas
+        .iter()
+        .enumerate()
+        .map(|(i, meta)| (meta.name.as_str(), i))
+        .collect();
+    // 每个任务沿 depends_on 能到达的全部上游
+    let reach: Vec<HashSet<usize>> = (0..metas.len())
+        .map(|start| {
+            let mut seen = HashSet::new();
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for upstream in &metas[node].depends_on {
+                    if let Some(&next) = index.get(upstream.as_str())
+                        && seen.insert(next)
+                    {
+                        stack.push(next);
+                    }
+                }
+            }
+            seen
         })
+        .collect();
+
+    let mut assigned = HashSet::new();
+    let mut cycles = Vec::new();
+    for i in 0..metas.len() {
+        if !reach[i].contains(&i) || assigned.contains(&i) {
+            continue;
+        }
+        let mut cycle: Vec<String> = (0..metas.len())
+            .filter(|&j| reach[i].contains(&j) && reach[j].contains(&i))
+            .map(|j| {
+                assigned.insert(j);
+                metas[j].name.clone()
+            })
+            .collect();
+        cycle.sort();
+        cycles.push(cycle);
     }
+    cycles
 }
 
-/// 将 TaskMeta 列表和命令表合并，生成运行时 Task 列表
-pub fn build_tasks_from_meta(metas: &[TaskMeta], cmd_map: &HashMap<String, CmdFn>) -> Vec<Task> {
+/// 检测依赖环并记录错误日志，返回处于环中、不应加载的任务名
+pub fn cyclic_task_names(metas: &[TaskMeta]) -> HashSet<String> {
+    find_dependency_cycles(metas)
+        .into_iter()
+        .inspect(|cycle| error!("任务依赖存在环 [{}]，相关任务不加载", cycle.join(", ")))
+        .flatten()
+        .collect()
+}
+
+/// 将 TaskMeta 列表和命令表合并，生成运行时 Task 列表；依赖成环的任务不加载
+pub fn build_tasks_from_meta(metas: &[TaskMeta], cmd_map: &HashMap<String, Command>) -> Vec<Task> {
     let mut tasks = Vec::new();
+    let cyclic = cyclic_task_names(metas);
 
-    for meta in metas {
+    for meta in metas.iter().filter(|meta| !cyclic.contains(&meta.name)) {
         // 提前克隆需要的字段，避免闭包借用局部变量
         let name = meta.name.clone();
         let cmd = meta.cmd.clone();
         let url = meta.url.clone();
         let arg = meta.arg.clone();
         let cron_expr = meta.cron_expr.clone();
-        let retry_times = meta.retry_times;
 
-        if let Some(cmd_fn) = cmd_map.get(&cmd) {
+        if let Some(command) = cmd_map.get(&cmd) {
+            let retry_times = if command.retryable {
+                meta.retry_times
+            } else {
+                if meta.retry_times > 0 {
+                    warn!(
+                        "任务 [{}] 命令 '{}' 不支持重试，忽略 retry_times",
+                        name, cmd
+                    );
+                }
+                0
+            };
             // 找到命令：把 cmd_fn 和 arg 克隆到闭包里
-            let cmd_fn = cmd_fn.clone();
+            let cmd_fn = command.handler.clone();
             let url_for_closure = url.clone();
             let arg_for_closure = arg.clone();
+            let params_for_closure = meta.params.clone();
             // 构造 Task
             let task = Task::new(
                 &TaskMeta {
@@ -105,28 +464,25 @@ pub fn build_tasks_from_meta(metas: &[TaskMeta], cmd_map: &HashMap<String, CmdFn
                     url,
                     arg,
                     cron_expr: cron_expr.clone(),
+                    timezone: meta.timezone,
                     retry_times,
+                    timeout_secs: meta.timeout_secs,
+                    concurrency_policy: meta.concurrency_policy,
+                    depends_on: meta.depends_on.clone(),
+                    params: meta.params.clone(),
                 },
                 move || {
                     let cmd_fn = cmd_fn.clone();
                     let urls = url_for_closure.clone();
                     let arg = arg_for_closure.clone();
+                    let params = params_for_closure.clone();
                     let name_for_log = name.clone();
                     async move {
-                        let args = if arg.trim().is_empty() {
-                            urls.clone()
-                        } else {
-                            arg
-                        };
-                        if args.trim().is_empty() {
-                            return Err(format!(
-                                "Task '{}' missing required url/args",
-                                name_for_log
-                            ));
-                        }
+                        // 参数是否齐全由命令的参数 schema 在保存任务时校验
                         let input = CommandInput {
                             urls: Some(urls),
-                            args,
+                            args: arg,
+                            params,
                         };
                         cmd_fn(input)
                             .await
@@ -145,6 +501,30 @@ pub fn build_tasks_from_meta(metas: &[TaskMeta], cmd_map: &HashMap<String, CmdFn
     tasks
 }
 
+/// 任务触发方式，对应 scheduled_task_run.trigger_type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskTrigger {
+    /// cron 到点触发
+    Cron,
+    /// 手动立即执行
+    Manual,
+    /// 启动时补跑错过的触发
+    Misfire,
+    /// 上游任务执行成功后触发
+    Dependency,
+}
+
+impl TaskTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskTrigger::Cron => "cron",
+            TaskTrigger::Manual => "manual",
+            TaskTrigger::Misfire => "misfire",
+            TaskTrigger::Dependency => "dependency",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TaskResult {
     pub name: String,
@@ -152,4 +532,287 @@ pub struct TaskResult {
     pub last_run: chrono::DateTime<Utc>,
     pub next_run: Option<chrono::DateTime<Utc>>,
     pub last_status: String,
+    /// 触发方式
+    pub trigger: TaskTrigger,
+    /// 预先创建的执行记录 id（手动触发时由调用方提供）
+    pub run_id: Option<i64>,
+    /// 本次执行结束时间
+    pub finished_at: chrono::DateTime<Utc>,
+    /// 实际尝试次数（首次 + 重试）
+    pub attempts: u32,
+    /// 各次失败尝试的错误信息，换行拼接
+    pub error: Option<String>,
+    /// 重试退避累计等待时长（毫秒）
+    pub backoff_ms: u64,
+    /// 任务返回的附加说明
+    pub message: Option<String>,
+}
+
+impl TaskResult {
+    /// 本次执行产出的结果条目数
+    pub fn item_count(&self) -> usize {
+        self.result
+            .as_ref()
+            .map(|r| r.values().map(|items| items.len()).sum())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConcurrencyPolicy, ExecutionOptions, MisfirePolicy, TaskMeta, TaskResult, TaskSchedule,
+        build_tasks_from_meta, find_dependency_cycles, parse_cron, parse_timezone,
+    };
+    use crate::scheduler::Scheduler;
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+    use cron::Schedule;
+    use serde_json::{Value, json};
+    use service::timer_task_command::{Command, CommandInput, build_cmd_map};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::mpsc;
+
+    fn daily_at_one() -> TaskSchedule {
+        TaskSchedule::new(Schedule::from_str("0 0 1 * * * *").unwrap(), Tz::UTC)
+    }
+
+    #[test]
+    fn parse_cron_requires_seconds_field() {
+        assert!(parse_cron(" 0 */5 * * * * ").is_ok());
+        assert!(parse_cron("0 0 1 * * * 2030").is_ok());
+        assert_eq!(parse_cron("  ").unwrap_err(), "cron 不能为空");
+
+        let unix = parse_cron("*/5 * * * *").unwrap_err();
+        assert!(unix.contains("5 段"), "{unix}");
+        assert!(unix.contains("'0 */5 * * * *'"), "{unix}");
+
+        let invalid = parse_cron("0 61 * * * *").unwrap_err();
+        assert!(invalid.contains("秒 分 时 日 月 周"), "{invalid}");
+    }
+
+    #[test]
+    fn parse_timezone_accepts_iana_names_only() {
+        assert_eq!(parse_timezone("UTC").unwrap(), Tz::UTC);
+        assert_eq!(
+            parse_timezone(" Asia/Shanghai ").unwrap(),
+            Tz::Asia__Shanghai
+        );
+        assert!(parse_timezone("Beijing").is_err());
+        assert!(parse_timezone("+08:00").is_err());
+        assert!(parse_timezone("").is_err());
+    }
+
+    #[test]
+    fn task_schedule_interprets_cron_in_task_timezone() {
+        // 北京时间 6-23、0-1 点每 10 分钟的第 37 秒
+        let schedule = Schedule::from_str("37 */10 6-23,0-1 * * * *").unwrap();
+        // UTC 17:55 即北京时间 01:55，下一次在北京时间 06:00:37（UTC 22:00:37）
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 17, 55, 0).unwrap();
+
+        let shanghai = TaskSchedule::new(schedule.clone(), Tz::Asia__Shanghai);
+        assert_eq!(
+            shanghai.after(&now).next().unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 1, 22, 0, 37).unwrap()
+        );
+
+        let utc = TaskSchedule::new(schedule, Tz::UTC);
+        assert_eq!(
+            utc.after(&now).next().unwrap(),
+            Utc.with_ymd_and_hms(2026, 1, 1, 18, 0, 37).unwrap()
+        );
+    }
+
+    #[test]
+    fn task_schedule_handles_daylight_saving_transitions() {
+        // 纽约 2026-03-08 02:00 跳到 03:00，每日 02:30 的触发在当天不存在
+        let schedule = TaskSchedule::new(
+            Schedule::from_str("0 30 2 * * * *").unwrap(),
+            Tz::America__New_York,
+        );
+        let now = Utc.with_ymd_and_hms(2026, 3, 7, 12, 0, 0).unwrap();
+        let runs: Vec<_> = schedule.after(&now).take(2).collect();
+
+        // 3 月 7 日 02:30 EST = 07:30 UTC 已过，下一次为 3 月 9 日 02:30 EDT = 06:30 UTC
+        assert_eq!(runs[0], Utc.with_ymd_and_hms(2026, 3, 9, 6, 30, 0).unwrap());
+        assert_eq!(
+            runs[1],
+            Utc.with_ymd_and_hms(2026, 3, 10, 6, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn execution_options_default_when_params_missing() {
+        let options = ExecutionOptions::from_params(&json!({"cmd": "health_check"})).unwrap();
+        assert_eq!(options, ExecutionOptions::default());
+    }
+
+    #[test]
+    fn execution_options_parse_timeout_and_policy() {
+        let options = ExecutionOptions::from_params(&json!({
+            "timeout_secs": 30,
+            "concurrency_policy": "queue_one"
+        }))
+        .unwrap();
+        assert_eq!(options.timeout_secs, Some(30));
+        assert_eq!(options.concurrency_policy, ConcurrencyPolicy::QueueOne);
+    }
+
+    #[test]
+    fn execution_options_reject_invalid_values() {
+        assert!(ExecutionOptions::from_params(&json!({"timeout_secs": 0})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"timeout_secs": "10"})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"concurrency_policy": "never"})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"concurrency_policy": 1})).is_err());
+    }
+
+    #[test]
+    fn execution_options_parse_misfire_policy() {
+        let options = ExecutionOptions::from_params(&json!({
+            "misfire_policy": "run_all_missed",
+            "misfire_max_runs": 3
+        }))
+        .unwrap();
+        assert_eq!(options.misfire_policy, MisfirePolicy::RunAllMissed);
+        assert_eq!(options.misfire_max_runs, 3);
+        assert!(ExecutionOptions::from_params(&json!({"misfire_policy": "all"})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"misfire_max_runs": 0})).is_err());
+    }
+
+    #[test]
+    fn missed_runs_is_zero_when_next_run_in_future() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 30, 0).unwrap();
+        let next_run = Utc.with_ymd_and_hms(2026, 1, 1, 1, 0, 0).unwrap();
+        for policy in [
+            MisfirePolicy::Ignore,
+            MisfirePolicy::RunOnce,
+            MisfirePolicy::RunAllMissed,
+        ] {
+            assert_eq!(policy.missed_runs(&daily_at_one(), next_run, now, 10), 0);
+        }
+    }
+
+    #[test]
+    fn missed_runs_follows_policy() {
+        // 1 日 01:00 起停机到 4 日 12:00，错过 1、2、3、4 日共 4 次
+        let next_run = Utc.with_ymd_and_hms(2026, 1, 1, 1, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 4, 12, 0, 0).unwrap();
+        let schedule = daily_at_one();
+
+        assert_eq!(
+            MisfirePolicy::Ignore.missed_runs(&schedule, next_run, now, 10),
+            0
+        );
+        assert_eq!(
+            MisfirePolicy::RunOnce.missed_runs(&schedule, next_run, now, 10),
+            1
+        );
+        assert_eq!(
+            MisfirePolicy::RunAllMissed.missed_runs(&schedule, next_run, now, 10),
+            4
+        );
+        assert_eq!(
+            MisfirePolicy::RunAllMissed.missed_runs(&schedule, next_run, now, 2),
+            2
+        );
+    }
+
+    #[test]
+    fn execution_options_parse_depends_on() {
+        let options = ExecutionOptions::from_params(&json!({
+            "depends_on": [" 抓取全量新闻数据 ", "抓取全量新闻数据", "提取新闻事件"]
+        }))
+        .unwrap();
+        assert_eq!(options.depends_on, vec!["抓取全量新闻数据", "提取新闻事件"]);
+        assert!(ExecutionOptions::from_params(&json!({"depends_on": "a"})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"depends_on": [""]})).is_err());
+        assert!(ExecutionOptions::from_params(&json!({"depends_on": [1]})).is_err());
+    }
+
+    fn meta(name: &str, depends_on: &[&str]) -> TaskMeta {
+        TaskMeta {
+            name: name.into(),
+            cmd: "health_check".into(),
+            url: String::new(),
+            arg: String::new(),
+            cron_expr: "0 0 * * * *".into(),
+            timezone: Tz::UTC,
+            retry_times: 0,
+            timeout_secs: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            params: Value::Null,
+        }
+    }
+
+    #[test]
+    fn find_dependency_cycles_accepts_chains_and_diamonds() {
+        let metas = vec![
+            meta("fetch", &[]),
+            meta("extract", &["fetch"]),
+            meta("keywords", &["extract"]),
+            meta("event", &["extract"]),
+            meta("merge", &["keywords", "event", "missing"]),
+        ];
+        assert!(find_dependency_cycles(&metas).is_empty());
+    }
+
+    #[test]
+    fn find_dependency_cycles_reports_each_cycle() {
+        let metas = vec![
+            meta("a", &["c"]),
+            meta("b", &["a"]),
+            meta("c", &["b"]),
+            meta("self", &["self"]),
+            meta("downstream", &["a"]),
+            meta("root", &[]),
+        ];
+        assert_eq!(
+            find_dependency_cycles(&metas),
+            vec![vec!["a", "b", "c"], vec!["self"]]
+        );
+    }
+
+    #[tokio::test]
+    async fn non_retryable_command_runs_once_per_execution() {
+        assert!(!build_cmd_map()["health_check"].retryable);
+
+        // 模拟健康检测：每次调用写一条历史并返回失败
+        let history_rows = Arc::new(AtomicUsize::new(0));
+        let rows = history_rows.clone();
+        let cmd_map = HashMap::from([(
+            "health_check".to_string(),
+            Command {
+                description: "检测服务健康状态",
+                retryable: false,
+                params: vec![],
+                handler: Arc::new(move |_: CommandInput| {
+                    let rows = rows.clone();
+                    Box::pin(async move {
+                        rows.fetch_add(1, Ordering::SeqCst);
+                        Err("健康检测未通过 1/1".to_string())
+                    })
+                }),
+            },
+        )]);
+        let tasks = build_tasks_from_meta(
+            &[TaskMeta {
+                retry_times: 3,
+                ..meta("health", &[])
+            }],
+            &cmd_map,
+        );
+        assert_eq!(tasks[0].retry_times, 0);
+
+        let scheduler = Scheduler::new(vec![], Some(1));
+        let (tx, mut rx) = mpsc::channel::<TaskResult>(1);
+        scheduler.run_now(tasks[0].clone(), tx, None).unwrap();
+        let result = rx.recv().await.unwrap();
+        assert_eq!(result.last_status, "failed");
+        assert_eq!(result.attempts, 1);
+        assert_eq!(history_rows.load(Ordering::SeqCst), 1);
+    }
 }
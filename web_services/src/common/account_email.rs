@@ -0,0 +1,195 @@
+use crate::common::AppState;
+use common::po::{EmailTokenPurpose, UserEmailTarget};
+use infra::create_user_email_token;
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use sha2::{Digest, Sha256};
+
+/// 邮箱验证链接有效期（分钟）
+pub const EMAIL_VERIFICATION_TTL_MINUTES: i64 = 24 * 60;
+/// 重置密码链接有效期（分钟）
+pub const PASSWORD_RESET_TTL_MINUTES: i64 = 30;
+/// 同一用户同一用途的邮件最短发送间隔（秒）
+pub const EMAIL_TOKEN_COOLDOWN_SECS: i64 = 60;
+
+/// 生成放进邮件链接的随机 token（64 位字母数字）
+pub fn generate_email_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
+
+/// 库中只保存 token 的 SHA-256 摘要
+pub fn hash_email_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// 转义插入 HTML 文本和属性值的内容
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 邮件主题、HTML 正文与纯文本正文，HTML 中插入的内容均经过转义
+fn build_email(
+    purpose: EmailTokenPurpose,
+    frontend_base_url: &str,
+    username: Option<&str>,
+    token: &str,
+) -> (String, String, String) {
+    let base_url = frontend_base_url.trim_end_matches('/');
+    let name = username.unwrap_or("你好");
+    let (subject, path, action, ttl) = match purpose {
+        EmailTokenPurpose::VerifyEmail => {
+            ("验证你的 Agora 邮箱", "verify-email", "验证邮箱", "24 小时")
+        }
+        EmailTokenPurpose::ResetPassword => (
+            "重置你的 Agora 密码",
+            "reset-password",
+            "重置密码",
+            "30 分钟",
+        ),
+    };
+    let link = format!("{base_url}/{path}?token={token}");
+    let html = format!(
+        "<p>{html_name}：</p><p>请点击下面的链接{action}，链接 {ttl} 内有效且只能使用一次：</p>\
+         <p><a href=\"{html_link}\">{html_link}</a></p><p>如果这不是你本人的操作，请忽略本邮件。</p>",
+        html_name = escape_html(name),
+        html_link = escape_html(&link),
+    );
+    let text = format!(
+        "{name}：\n请打开下面的链接{action}，链接 {ttl} 内有效且只能使用一次：\n{link}\n如果这不是你本人的操作，请忽略本邮件。"
+    );
+    (subject.to_string(), html, text)
+}
+
+/// 签发一次性 token 并在后台发送邮件，发送失败只记录日志
+///
+/// 冷却期内已发送过同用途邮件时不再发送，返回 false
+pub async fn send_account_email(
+    app_state: &AppState,
+    target: &UserEmailTarget,
+    purpose: EmailTokenPurpose,
+) -> anyhow::Result<bool> {
+    let token = generate_email_token();
+    let ttl_minutes = match purpose {
+        EmailTokenPurpose::VerifyEmail => EMAIL_VERIFICATION_TTL_MINUTES,
+        EmailTokenPurpose::ResetPassword => PASSWORD_RESET_TTL_MINUTES,
+    };
+
+    let created = create_user_email_token(
+        target.id,
+        purpose,
+        &hash_email_token(&token),
+        &target.email,
+        ttl_minutes,
+        EMAIL_TOKEN_COOLDOWN_SECS,
+        &app_state.db_pool,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "签发用户 {} 的 {} token 失败: {e:?}",
+            target.id,
+            purpose.as_str()
+        );
+        anyhow::anyhow!("签发邮件 token 失败")
+    })?;
+    if !created {
+        return Ok(false);
+    }
+
+    let (subject, html, text) = build_email(
+        purpose,
+        &app_state.configuration.application.frontend_base_url,
+        target.username.as_deref(),
+        &token,
+    );
+    let email_client = app_state.email_client.clone();
+    let recipient = target.email.clone();
+    // 在后台发送，接口耗时不随邮件服务变化，也不暴露账户是否存在
+    actix_web::rt::spawn(async move {
+        let _ = email_client
+            .send_email(&recipient, &subject, &html, &text)
+            .await;
+    });
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_token_is_random_and_hashed_to_hex() {
+        let token = generate_email_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_email_token());
+
+        let hash = hash_email_token(&token);
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, hash_email_token(&token));
+        assert_eq!(
+            hash_email_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn build_email_links_to_frontend_page() {
+        let (subject, html, text) = build_email(
+            EmailTokenPurpose::ResetPassword,
+            "https://agora.example.com/",
+            Some("alice"),
+            "tok123",
+        );
+
+        assert_eq!(subject, "重置你的 Agora 密码");
+        let link = "https://agora.example.com/reset-password?token=tok123";
+        assert!(html.contains(link));
+        assert!(text.contains(link));
+        assert!(text.starts_with("alice："));
+    }
+
+    #[test]
+    fn build_email_escapes_html_in_username() {
+        let username = r#"<a href="https://evil.example.com">点我</a>"#;
+        let (_, html, text) = build_email(
+            EmailTokenPurpose::VerifyEmail,
+            "https://agora.example.com",
+            Some(username),
+            "tok123",
+        );
+
+        assert!(!html.contains("evil.example.com\">"));
+        assert!(html.starts_with(
+            "<p>&lt;a href=&quot;https://evil.example.com&quot;&gt;点我&lt;/a&gt;：</p>"
+        ));
+        assert_eq!(html.matches("<a ").count(), 1);
+        // 纯文本正文不做转义
+        assert!(text.starts_with(username));
+    }
+
+    #[test]
+    fn escape_html_escapes_markup_and_quotes() {
+        assert_eq!(
+            escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+        assert_eq!(escape_html("张三"), "张三");
+    }
+}
@@ -1,5 +1,6 @@
+use crate::common::UserAuthCache;
 use crate::task_manage::{TaskManager, get_global_task_manager};
-use infra::{OAuthConfig, Setting};
+use infra::{EmailClient, OAuthConfig, Setting};
 use oauth2::basic::BasicClient;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -16,6 +17,10 @@ pub struct AppState {
     pub task_manager: Arc<TaskManager>,
     // 全局配置文件配置
     pub configuration: Setting,
+    // 鉴权中间件使用的账户状态缓存
+    pub user_auth_cache: Arc<UserAuthCache>,
+    // 发送验证 / 重置密码邮件
+    pub email_client: EmailClient,
 }
 
 impl AppState {
@@ -30,12 +35,16 @@ impl AppState {
         let task_manager =
             get_global_task_manager().ok_or_else(|| anyhow::anyhow!("TaskManager 尚未初始化"))?;
 
+        let email_client = EmailClient::from_settings(&configuration.email_client)?;
+
         Ok(Self {
             oauth_config,
             oauth_client,
             db_pool,
             task_manager,
             configuration,
+            user_auth_cache: Arc::new(UserAuthCache::default()),
+            email_client,
         })
     }
 }
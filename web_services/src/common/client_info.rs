@@ -0,0 +1,71 @@
+use actix_web::HttpRequest;
+use actix_web::http::header::USER_AGENT;
+
+/// User-Agent 最多保留的字符数，超出部分截断
+const MAX_USER_AGENT_CHARS: usize = 512;
+/// 与 refresh_tokens.ip_address 的列宽一致
+const MAX_IP_ADDRESS_CHARS: usize = 64;
+
+/// 签发 refresh_token 时记录的客户端信息，用于会话列表展示
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl ClientInfo {
+    /// IP 优先取反向代理转发的真实地址（Forwarded / X-Forwarded-For），其次取连接对端地址
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let user_agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.chars().take(MAX_USER_AGENT_CHARS).collect());
+        let ip_address = req
+            .connection_info()
+            .realip_remote_addr()
+            .map(|s| s.chars().take(MAX_IP_ADDRESS_CHARS).collect());
+
+        Self {
+            user_agent,
+            ip_address,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn reads_user_agent_and_forwarded_ip() {
+        let req = TestRequest::default()
+            .insert_header((USER_AGENT, "Mozilla/5.0"))
+            .insert_header(("X-Forwarded-For", "203.0.113.7"))
+            .to_http_request();
+
+        assert_eq!(
+            ClientInfo::from_request(&req),
+            ClientInfo {
+                user_agent: Some("Mozilla/5.0".into()),
+                ip_address: Some("203.0.113.7".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn truncates_long_user_agent() {
+        let req = TestRequest::default()
+            .insert_header((USER_AGENT, "a".repeat(MAX_USER_AGENT_CHARS + 10)))
+            .to_http_request();
+
+        let info = ClientInfo::from_request(&req);
+        assert_eq!(
+            info.user_agent.map(|s| s.chars().count()),
+            Some(MAX_USER_AGENT_CHARS)
+        );
+    }
+}
@@ -1,5 +1,11 @@
+mod account_email;
 mod app_state;
+mod client_info;
 mod token;
+mod user_auth_cache;
 
+pub use account_email::*;
 pub use app_state::*;
+pub use client_info::*;
 pub use token::*;
+pub use user_auth_cache::*;
@@ -0,0 +1,87 @@
+use common::po::UserAuthState;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 账户状态在进程内的缓存时长，过期后重新查询 user_info
+pub const USER_AUTH_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 鉴权中间件使用的账户状态缓存，避免每个请求都查询一次数据库
+///
+/// 只缓存在当前进程内：本实例修改 token 版本或账户状态后主动失效对应条目，
+/// 其他实例最迟在 TTL 到期后感知变更
+pub struct UserAuthCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<i64, (Instant, UserAuthState)>>,
+}
+
+impl UserAuthCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 读取未过期的账户状态
+    pub fn get(&self, user_id: i64) -> Option<UserAuthState> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&user_id)
+            .filter(|(cached_at, _)| cached_at.elapsed() < self.ttl)
+            .map(|(_, state)| state.clone())
+    }
+
+    /// 写入账户状态，顺带清理已过期的条目
+    pub fn insert(&self, user_id: i64, state: UserAuthState) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (cached_at, _)| cached_at.elapsed() < self.ttl);
+        entries.insert(user_id, (Instant::now(), state));
+    }
+
+    /// 账户状态变更后立即失效缓存
+    pub fn invalidate(&self, user_id: i64) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(&user_id);
+    }
+}
+
+impl Default for UserAuthCache {
+    fn default() -> Self {
+        Self::new(USER_AUTH_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserAuthCache;
+    use common::po::UserAuthState;
+    use std::time::Duration;
+
+    fn active(token_version: i64) -> UserAuthState {
+        UserAuthState {
+            token_version,
+            status: "active".into(),
+        }
+    }
+
+    #[test]
+    fn cache_returns_entry_until_invalidated() {
+        let cache = UserAuthCache::new(Duration::from_secs(30));
+        cache.insert(1, active(3));
+
+        assert_eq!(cache.get(1), Some(active(3)));
+        assert_eq!(cache.get(2), None);
+
+        cache.invalidate(1);
+        assert_eq!(cache.get(1), None);
+    }
+
+    #[test]
+    fn cache_drops_expired_entries() {
+        let cache = UserAuthCache::new(Duration::ZERO);
+        cache.insert(1, active(0));
+
+        assert_eq!(cache.get(1), None);
+    }
+}
@@ -15,6 +15,7 @@ async fn main() -> Result<()> {
     // 读取配置文件
     let configuration = get_configuration(Some(PathBuf::from("./configuration")))
         .context("Failed to read configuration")?;
+    configuration.validate().context("Invalid configuration")?;
 
     // 创建数据库连接池
     let connection_pool = create_database_pool(&configuration)
@@ -1,14 +1,79 @@
-use crate::common::ExtractToken;
+use crate::common::{AppState, ExtractToken};
 use actix_web::body::BoxBody;
 use actix_web::{
     Error, HttpMessage, HttpResponse,
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web,
 };
 use common::api::ApiResponse;
-use common::utils::verify_jwt;
+use common::po::UserAuthState;
+use common::utils::{JwtClaims, verify_jwt};
 use futures::future::{LocalBoxFuture, Ready, ok};
+use infra::get_user_auth_state;
 use std::rc::Rc;
 
+/// JWT 对应账户的核对结果
+#[derive(Debug, PartialEq)]
+enum AccountCheck {
+    Valid,
+    /// 用户不存在或 token 版本已变更
+    TokenRevoked,
+    /// 账户被停用 / 冻结等
+    AccountUnavailable,
+}
+
+/// 比对 JWT 中的 ver 与账户当前的 token 版本和状态
+fn check_account(claims: &JwtClaims, state: Option<&UserAuthState>) -> AccountCheck {
+    match state {
+        None => AccountCheck::TokenRevoked,
+        Some(state) if state.token_version != claims.ver => AccountCheck::TokenRevoked,
+        Some(state) if state.status != "active" => AccountCheck::AccountUnavailable,
+        Some(_) => AccountCheck::Valid,
+    }
+}
+
+/// 优先读取缓存，未命中时查询 user_info 并写入缓存
+async fn load_user_auth_state(
+    app_state: &AppState,
+    user_id: i64,
+) -> anyhow::Result<Option<UserAuthState>> {
+    if let Some(state) = app_state.user_auth_cache.get(user_id) {
+        return Ok(Some(state));
+    }
+
+    let state = get_user_auth_state(user_id, &app_state.db_pool).await?;
+    if let Some(state) = &state {
+        app_state.user_auth_cache.insert(user_id, state.clone());
+    }
+    Ok(state)
+}
+
+/// 核对账户状态，未通过时返回应直接响应给客户端的错误
+async fn verify_account(req: &ServiceRequest, claims: &JwtClaims) -> Result<(), HttpResponse> {
+    let Some(app_state) = req.app_data::<web::Data<AppState>>() else {
+        tracing::error!("AuthMiddleware 未找到 AppState");
+        return Err(HttpResponse::InternalServerError()
+            .json(ApiResponse::<()>::err("Internal server error")));
+    };
+
+    let state = load_user_auth_state(app_state, claims.uid)
+        .await
+        .map_err(|_| {
+            HttpResponse::InternalServerError()
+                .json(ApiResponse::<()>::err("Internal server error"))
+        })?;
+
+    match check_account(claims, state.as_ref()) {
+        AccountCheck::Valid => Ok(()),
+        AccountCheck::TokenRevoked => {
+            Err(HttpResponse::Unauthorized().json(ApiResponse::<()>::err("Token revoked")))
+        }
+        AccountCheck::AccountUnavailable => {
+            Err(HttpResponse::Forbidden().json(ApiResponse::<()>::err("Account unavailable")))
+        }
+    }
+}
+
 pub struct AuthMiddleware;
 
 impl<S> Transform<S, ServiceRequest> for AuthMiddleware
@@ -57,6 +122,9 @@ where
             match token_opt {
                 Some(token) => match verify_jwt(&token) {
                     Ok(claims) => {
+                        if let Err(resp) = verify_account(&req, &claims).await {
+                            return Ok(req.into_response(resp.map_into_boxed_body()));
+                        }
                         req.extensions_mut().insert(claims);
                         srv.call(req).await
                     }
@@ -108,3 +176,58 @@ mod tests {
         assert_eq!(data["message"], "Missing token");
     }
 }
+
+#[cfg(test)]
+mod account_check_tests {
+    use super::*;
+
+    fn claims_with_ver(ver: i64) -> JwtClaims {
+        JwtClaims {
+            sub: "u1".into(),
+            exp: 0,
+            iat: 0,
+            uid: 1,
+            email: None,
+            avatar: None,
+            roles: vec!["user".into()],
+            ver,
+        }
+    }
+
+    fn state(token_version: i64, status: &str) -> UserAuthState {
+        UserAuthState {
+            token_version,
+            status: status.into(),
+        }
+    }
+
+    #[test]
+    fn check_account_accepts_matching_version_of_active_user() {
+        assert_eq!(
+            check_account(&claims_with_ver(2), Some(&state(2, "active"))),
+            AccountCheck::Valid
+        );
+    }
+
+    #[test]
+    fn check_account_rejects_stale_version_or_missing_user() {
+        assert_eq!(
+            check_account(&claims_with_ver(1), Some(&state(2, "active"))),
+            AccountCheck::TokenRevoked
+        );
+        assert_eq!(
+            check_account(&claims_with_ver(1), None),
+            AccountCheck::TokenRevoked
+        );
+    }
+
+    #[test]
+    fn check_account_rejects_suspended_or_frozen_user() {
+        for status in ["suspended", "frozen", "inactive"] {
+            assert_eq!(
+                check_account(&claims_with_ver(0), Some(&state(0, status))),
+                AccountCheck::AccountUnavailable
+            );
+        }
+    }
+}
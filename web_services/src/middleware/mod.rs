@@ -1,5 +1,7 @@
 mod auth_middleware;
 mod charset_middleware;
+mod permission_middleware;
 
 pub use auth_middleware::*;
 pub use charset_middleware::*;
+pub use permission_middleware::*;
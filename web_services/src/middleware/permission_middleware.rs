@@ -0,0 +1,214 @@
+use crate::common::AppState;
+use actix_web::body::BoxBody;
+use actix_web::{
+    Error, HttpMessage, ResponseError,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web,
+};
+use common::api::ApiError;
+use common::utils::JwtClaims;
+use futures::future::{LocalBoxFuture, Ready, ok};
+use infra::list_user_permissions;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// 拥有该权限（或 admin 角色）即视为拥有全部权限
+pub const ADMIN_PERMISSION: &str = "admin:all";
+
+/// 当前请求用户的有效权限（角色权限 ∪ 套餐权限）
+///
+/// 首次校验时查询并写入请求扩展，同一请求经过多层 `RequirePermission` 时不再重复查询
+#[derive(Debug, Clone, Default)]
+pub struct EffectivePermissions(HashSet<String>);
+
+impl EffectivePermissions {
+    pub fn new(permissions: impl IntoIterator<Item = String>) -> Self {
+        Self(permissions.into_iter().collect())
+    }
+
+    pub fn allows(&self, claims: &JwtClaims, permission: &str) -> bool {
+        claims.roles.iter().any(|r| r == "admin")
+            || self.0.contains(ADMIN_PERMISSION)
+            || self.0.contains(permission)
+    }
+}
+
+/// 要求当前用户拥有指定权限，缺少时统一返回 403
+///
+/// 依赖 `AuthMiddleware` 写入的 `JwtClaims`，因此要包在 `AuthMiddleware` 内层，例如：
+/// `web::scope("/admin").wrap(RequirePermission("admin:all")).wrap(AuthMiddleware)`
+pub struct RequirePermission(pub &'static str);
+
+impl<S> Transform<S, ServiceRequest> for RequirePermission
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RequirePermissionService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequirePermissionService {
+            service: Rc::new(service),
+            permission: self.0,
+        })
+    }
+}
+
+pub struct RequirePermissionService<S> {
+    service: Rc<S>,
+    permission: &'static str,
+}
+
+impl<S> Service<ServiceRequest> for RequirePermissionService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let srv = Rc::clone(&self.service);
+        let permission = self.permission;
+
+        Box::pin(async move {
+            match check_permission(&req, permission).await {
+                Ok(()) => srv.call(req).await,
+                Err(e) => Ok(req.into_response(e.error_response())),
+            }
+        })
+    }
+}
+
+/// 读取（或首次查询）当前用户的有效权限并校验
+async fn check_permission(req: &ServiceRequest, permission: &str) -> Result<(), ApiError> {
+    let claims = req
+        .extensions()
+        .get::<JwtClaims>()
+        .cloned()
+        .ok_or_else(|| ApiError::Unauthorized("未授权".into()))?;
+
+    let cached = req.extensions().get::<EffectivePermissions>().cloned();
+    let permissions = match cached {
+        Some(permissions) => permissions,
+        None => {
+            let app_state = req.app_data::<web::Data<AppState>>().ok_or_else(|| {
+                tracing::error!("RequirePermission 未找到 AppState");
+                ApiError::Internal("服务器内部错误".into())
+            })?;
+            let permissions = list_user_permissions(claims.uid, &app_state.db_pool)
+                .await
+                .map(EffectivePermissions::new)
+                .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+            req.extensions_mut().insert(permissions.clone());
+            permissions
+        }
+    };
+
+    if permissions.allows(&claims, permission) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(format!("需要 {permission} 权限")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{TestRequest, call_service, init_service};
+    use actix_web::{App, HttpResponse, body::to_bytes, http::StatusCode, web};
+    use serde_json::Value;
+
+    fn build_claims(roles: Vec<&str>) -> JwtClaims {
+        JwtClaims {
+            sub: "u1".into(),
+            exp: 0,
+            iat: 0,
+            uid: 1,
+            email: None,
+            avatar: None,
+            roles: roles.into_iter().map(str::to_string).collect(),
+            ver: 1,
+        }
+    }
+
+    async fn protected() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    #[test]
+    fn allows_admin_role_or_admin_permission() {
+        let none = EffectivePermissions::default();
+        assert!(none.allows(&build_claims(vec!["admin"]), "task:write"));
+
+        let admin_all = EffectivePermissions::new(["admin:all".to_string()]);
+        assert!(admin_all.allows(&build_claims(vec!["user"]), "news:curate"));
+    }
+
+    #[test]
+    fn allows_only_granted_permissions() {
+        let permissions = EffectivePermissions::new(["task:read".to_string()]);
+        let claims = build_claims(vec!["editor"]);
+
+        assert!(permissions.allows(&claims, "task:read"));
+        assert!(!permissions.allows(&claims, "task:write"));
+    }
+
+    #[actix_web::test]
+    async fn require_permission_rejects_request_without_claims() {
+        let app = init_service(
+            App::new().service(
+                web::scope("/api")
+                    .wrap(RequirePermission("task:read"))
+                    .route("/protected", web::get().to(protected)),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/api/protected").to_request();
+        let resp = call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn require_permission_reuses_resolved_permissions() {
+        let app = init_service(
+            App::new().service(
+                web::scope("/api")
+                    .wrap(RequirePermission("task:write"))
+                    .wrap(RequirePermission("task:read"))
+                    .wrap_fn(|req, srv| {
+                        req.extensions_mut().insert(build_claims(vec!["editor"]));
+                        req.extensions_mut()
+                            .insert(EffectivePermissions::new(["task:read".to_string()]));
+                        srv.call(req)
+                    })
+                    .route("/protected", web::get().to(protected)),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/api/protected").to_request();
+        let resp = call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let data: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(data["status"], "error");
+        assert_eq!(data["message"], "权限不足: 需要 task:write 权限");
+    }
+}
@@ -1,2 +1,8 @@
+mod news_sources;
 mod scheduled_task_reload;
+mod scheduler_status;
+mod users;
+pub use news_sources::*;
 pub use scheduled_task_reload::*;
+pub use scheduler_status::*;
+pub use users::*;
@@ -0,0 +1,246 @@
+use crate::common::AppState;
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use common::api::{ApiError, ApiResponse};
+use common::dto::{CreateNewsSourceDTO, UpdateNewsSourceDTO};
+use common::po::ApiResult;
+use infra::{create_news_source, delete_news_source, list_news_sources, update_news_source};
+use reqwest::Url;
+use serde::Deserialize;
+use service::news_source::{FETCH_KIND_AGGREGATOR, FETCH_KIND_HTML, FETCH_KIND_RSS};
+
+/// id / displayName / category 的长度上限，与 news_source 表一致
+const MAX_NAME_LEN: usize = 50;
+const MAX_LANGUAGE_LEN: usize = 16;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateNewsSourceReq {
+    id: String,
+    display_name: String,
+    category: Option<String>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    #[serde(default = "default_fetch_kind")]
+    fetch_kind: String,
+    url: Option<String>,
+    #[serde(default)]
+    priority: i32,
+    #[serde(default = "default_language")]
+    language: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateNewsSourceReq {
+    display_name: Option<String>,
+    category: Option<String>,
+    enabled: Option<bool>,
+    fetch_kind: Option<String>,
+    url: Option<String>,
+    priority: Option<i32>,
+    language: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_fetch_kind() -> String {
+    FETCH_KIND_AGGREGATOR.to_string()
+}
+
+fn default_language() -> String {
+    "zh".to_string()
+}
+
+/// 去掉首尾空白后校验非空和长度
+fn parse_text(field: &str, value: &str, max_len: usize) -> Result<String, ApiError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(ApiError::BadRequest(format!("{field} 不能为空")));
+    }
+    if value.chars().count() > max_len {
+        return Err(ApiError::BadRequest(format!(
+            "{field} 长度不能超过 {max_len} 个字符"
+        )));
+    }
+    Ok(value.to_string())
+}
+
+fn parse_fetch_kind(fetch_kind: &str) -> Result<String, ApiError> {
+    match fetch_kind.trim() {
+        kind @ (FETCH_KIND_AGGREGATOR | FETCH_KIND_RSS | FETCH_KIND_HTML) => Ok(kind.to_string()),
+        other => Err(ApiError::BadRequest(format!(
+            "fetchKind 只支持 aggregator / rss / html，收到 '{other}'"
+        ))),
+    }
+}
+
+fn parse_url(url: &str) -> Result<String, ApiError> {
+    let url = url.trim();
+    let parsed = Url::parse(url).map_err(|e| ApiError::BadRequest(format!("url 非法: {e}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(ApiError::BadRequest("url 只支持 http / https 地址".into()));
+    }
+    Ok(url.to_string())
+}
+
+fn into_create_dto(req: CreateNewsSourceReq) -> Result<CreateNewsSourceDTO, ApiError> {
+    Ok(CreateNewsSourceDTO {
+        id: parse_text("id", &req.id, MAX_NAME_LEN)?,
+        display_name: parse_text("displayName", &req.display_name, MAX_NAME_LEN)?,
+        category: req
+            .category
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| parse_text("category", &c, MAX_NAME_LEN))
+            .transpose()?,
+        enabled: req.enabled,
+        fetch_kind: parse_fetch_kind(&req.fetch_kind)?,
+        url: req
+            .url
+            .filter(|u| !u.trim().is_empty())
+            .map(|u| parse_url(&u))
+            .transpose()?,
+        priority: req.priority,
+        language: parse_text("language", &req.language, MAX_LANGUAGE_LEN)?,
+    })
+}
+
+fn into_update_dto(req: UpdateNewsSourceReq) -> Result<UpdateNewsSourceDTO, ApiError> {
+    Ok(UpdateNewsSourceDTO {
+        display_name: req
+            .display_name
+            .map(|name| parse_text("displayName", &name, MAX_NAME_LEN))
+            .transpose()?,
+        category: req
+            .category
+            .map(|c| parse_text("category", &c, MAX_NAME_LEN))
+            .transpose()?,
+        enabled: req.enabled,
+        fetch_kind: req.fetch_kind.map(|k| parse_fetch_kind(&k)).transpose()?,
+        url: req.url.map(|u| parse_url(&u)).transpose()?,
+        priority: req.priority,
+        language: req
+            .language
+            .map(|l| parse_text("language", &l, MAX_LANGUAGE_LEN))
+            .transpose()?,
+    })
+}
+
+#[get("")]
+async fn news_sources_list(app_state: web::Data<AppState>) -> ApiResult {
+    match list_news_sources(&app_state.db_pool).await {
+        Ok(sources) => Ok(HttpResponse::Ok().json(ApiResponse::ok(sources))),
+        Err(e) => {
+            tracing::error!("查询新闻源失败: {e:?}");
+            Err(ApiError::Database("数据库查询失败".into()))
+        }
+    }
+}
+
+#[post("")]
+async fn news_sources_create(
+    body: web::Json<CreateNewsSourceReq>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let dto = into_create_dto(body.into_inner())?;
+
+    match create_news_source(&dto, &app_state.db_pool).await {
+        Ok(Some(source)) => Ok(HttpResponse::Created().json(ApiResponse::ok(source))),
+        Ok(None) => Err(ApiError::BadRequest(format!("新闻源 {} 已存在", dto.id))),
+        Err(e) => {
+            tracing::error!("登记新闻源失败: {e:?}");
+            Err(ApiError::Internal("登记新闻源失败".into()))
+        }
+    }
+}
+
+#[put("/{id}")]
+async fn news_sources_update(
+    path: web::Path<String>,
+    body: web::Json<UpdateNewsSourceReq>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let id = path.into_inner();
+    let dto = into_update_dto(body.into_inner())?;
+
+    match update_news_source(&id, &dto, &app_state.db_pool).await {
+        Ok(source) => Ok(HttpResponse::Ok().json(ApiResponse::ok(source))),
+        Err(e) => {
+            tracing::error!("更新新闻源 {id} 失败: {e:?}");
+            Err(ApiError::NotFound(format!("新闻源 {id} 不存在或更新失败")))
+        }
+    }
+}
+
+#[delete("/{id}")]
+async fn news_sources_delete(path: web::Path<String>, app_state: web::Data<AppState>) -> ApiResult {
+    let id = path.into_inner();
+
+    match delete_news_source(&id, &app_state.db_pool).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(()))),
+        Err(e) => {
+            tracing::error!("删除新闻源 {id} 失败: {e:?}");
+            Err(ApiError::NotFound(format!("新闻源 {id} 不存在")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CreateNewsSourceReq, UpdateNewsSourceReq, into_create_dto, into_update_dto};
+
+    fn create_req(fetch_kind: &str, url: Option<&str>) -> CreateNewsSourceReq {
+        CreateNewsSourceReq {
+            id: " weibo ".into(),
+            display_name: "微博热搜".into(),
+            category: Some(" ".into()),
+            enabled: true,
+            fetch_kind: fetch_kind.into(),
+            url: url.map(str::to_string),
+            priority: 10,
+            language: "zh".into(),
+        }
+    }
+
+    #[test]
+    fn into_create_dto_trims_fields_and_validates_kind_and_url() {
+        let dto = into_create_dto(create_req("aggregator", None)).unwrap();
+        assert_eq!(dto.id, "weibo");
+        assert_eq!(dto.category, None);
+        assert_eq!(dto.fetch_kind, "aggregator");
+
+        assert_eq!(
+            into_create_dto(create_req("rss", Some("https://a.com/feed.xml")))
+                .unwrap()
+                .url
+                .as_deref(),
+            Some("https://a.com/feed.xml")
+        );
+        assert!(into_create_dto(create_req("sitemap", None)).is_err());
+        assert!(into_create_dto(create_req("rss", Some("ftp://a.com/feed"))).is_err());
+    }
+
+    #[test]
+    fn into_update_dto_rejects_blank_or_oversized_names() {
+        let req = |display_name: &str| UpdateNewsSourceReq {
+            display_name: Some(display_name.into()),
+            category: None,
+            enabled: Some(false),
+            fetch_kind: None,
+            url: None,
+            priority: None,
+            language: None,
+        };
+
+        assert_eq!(
+            into_update_dto(req(" 微博 "))
+                .unwrap()
+                .display_name
+                .as_deref(),
+            Some("微博")
+        );
+        assert!(into_update_dto(req("  ")).is_err());
+        assert!(into_update_dto(req(&"长".repeat(51))).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::common::AppState;
+use actix_web::{HttpResponse, get, web};
+use common::api::{ApiError, ApiResponse};
+use common::po::ApiResult;
+
+#[get("/scheduler/status")]
+async fn scheduler_status_get(app_state: web::Data<AppState>) -> ApiResult {
+    match app_state.task_manager.scheduler_status().await {
+        Ok(status) => Ok(HttpResponse::Ok().json(ApiResponse::ok(status))),
+        Err(e) => {
+            tracing::error!("查询调度器状态失败: {e:?}");
+            Err(ApiError::Internal("查询调度器状态失败".into()))
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::common::AppState;
+use actix_web::{HttpResponse, post, web};
+use common::api::{ApiError, ApiResponse};
+use common::dto::UserTokenVersionDTO;
+use common::po::ApiResult;
+use infra::{bump_user_token_version, reset_login_failures};
+
+/// 递增用户的 token 版本，该用户已签发的 JWT 和 refresh_token 全部失效
+#[post("/users/{id}/token-version")]
+async fn user_token_version_bump(
+    path: web::Path<i64>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let user_id = path.into_inner();
+
+    match bump_user_token_version(user_id, &app_state.db_pool).await {
+        Ok(Some(token_version)) => {
+            app_state.user_auth_cache.invalidate(user_id);
+            tracing::info!("用户 {user_id} 的 token 版本已递增至 {token_version}");
+            Ok(
+                HttpResponse::Ok().json(ApiResponse::ok(UserTokenVersionDTO {
+                    user_id,
+                    token_version,
+                })),
+            )
+        }
+        Ok(None) => Err(ApiError::NotFound(format!("用户 {user_id} 不存在"))),
+        Err(e) => {
+            tracing::error!("递增用户 {user_id} 的 token 版本失败: {e:?}");
+            Err(ApiError::Internal("递增 token 版本失败".into()))
+        }
+    }
+}
+
+/// 解除用户因连续登录失败导致的锁定，并清零失败次数
+#[post("/users/{id}/unlock")]
+async fn user_unlock(path: web::Path<i64>, app_state: web::Data<AppState>) -> ApiResult {
+    let user_id = path.into_inner();
+
+    match reset_login_failures(user_id, &app_state.db_pool).await {
+        Ok(true) => {
+            tracing::info!("用户 {user_id} 的登录锁定已解除");
+            Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(())))
+        }
+        Ok(false) => Err(ApiError::NotFound(format!("用户 {user_id} 不存在"))),
+        Err(e) => {
+            tracing::error!("解除用户 {user_id} 登录锁定失败: {e:?}");
+            Err(ApiError::Internal("解除登录锁定失败".into()))
+        }
+    }
+}
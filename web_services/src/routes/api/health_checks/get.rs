@@ -0,0 +1,39 @@
+use crate::common::AppState;
+use actix_web::{HttpResponse, get, web};
+use chrono::Utc;
+use common::HealthCheckFilter;
+use common::api::{ApiError, ApiResponse};
+use common::po::ApiResult;
+use infra::list_health_check_summaries;
+
+/// 默认统计最近 24 小时
+const DEFAULT_HOURS: i64 = 24;
+/// 最多统计最近 90 天
+const MAX_HOURS: i64 = 24 * 90;
+
+/// GET /api/health-checks — 按地址汇总健康检测的可用率和延迟
+#[get("/health-checks")]
+async fn health_checks_get(
+    query: web::Query<HealthCheckFilter>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let hours = query.hours.unwrap_or(DEFAULT_HOURS);
+    if !(1..=MAX_HOURS).contains(&hours) {
+        return Err(ApiError::BadRequest(format!(
+            "hours 取值范围为 1 ~ {MAX_HOURS}"
+        )));
+    }
+    let since = Utc::now() - chrono::Duration::hours(hours);
+    let url = query
+        .url
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    match list_health_check_summaries(since, url, &app_state.db_pool).await {
+        Ok(summaries) => Ok(HttpResponse::Ok().json(ApiResponse::ok(summaries))),
+        Err(e) => {
+            tracing::error!("查询健康检测汇总失败: {e:?}");
+            Err(ApiError::Database("数据库查询失败".into()))
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use crate::common::AppState;
-use actix_web::{HttpMessage, HttpRequest, HttpResponse, get, web};
+use crate::common::{AppState, send_account_email};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, get, post, web};
 use common::api::{ApiError, ApiResponse};
-use common::po::ApiResult;
+use common::po::{ApiResult, EmailTokenPurpose};
 use common::utils::JwtClaims;
+use infra::{find_user_email_target, list_user_permissions};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,25 +24,9 @@ async fn me(req: HttpRequest, app_state: web::Data<AppState>) -> ApiResult {
         .cloned()
         .ok_or_else(|| ApiError::Unauthorized("未携带或非法的 JWT".into()))?;
 
-    let permissions: Vec<String> = sqlx::query_scalar(
-        r#"
-            SELECT DISTINCT p.name
-            FROM permissions p
-            LEFT JOIN role_permissions rp ON rp.permission_id = p.id
-            LEFT JOIN user_roles ur ON ur.role_id = rp.role_id AND ur.user_id = $1
-            LEFT JOIN plan_permissions pp ON pp.permission_id = p.id
-            LEFT JOIN user_info ui ON ui.id = $1 AND ui.plan = pp.plan
-            WHERE ur.user_id IS NOT NULL OR ui.id IS NOT NULL
-            ORDER BY p.name
-        "#,
-    )
-    .bind(claims.uid)
-    .fetch_all(&app_state.db_pool)
-    .await
-    .map_err(|e| {
-        tracing::error!("查询用户权限失败: {e}");
-        ApiError::Internal("服务器内部错误".into())
-    })?;
+    let permissions = list_user_permissions(claims.uid, &app_state.db_pool)
+        .await
+        .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
 
     let user = UserView {
         id: claims.uid,
@@ -53,3 +38,30 @@ async fn me(req: HttpRequest, app_state: web::Data<AppState>) -> ApiResult {
     };
     Ok(HttpResponse::Ok().json(ApiResponse::ok(user)))
 }
+
+/// 重新发送邮箱验证邮件
+#[post("/me/email/verification")]
+async fn me_email_verification_send(req: HttpRequest, app_state: web::Data<AppState>) -> ApiResult {
+    let claims = req
+        .extensions()
+        .get::<JwtClaims>()
+        .cloned()
+        .ok_or_else(|| ApiError::Unauthorized("未携带或非法的 JWT".into()))?;
+
+    let target = find_user_email_target(Some(claims.uid), None, &app_state.db_pool)
+        .await
+        .map_err(|_| ApiError::Internal("服务器内部错误".into()))?
+        .ok_or_else(|| ApiError::BadRequest("账号未填写邮箱".into()))?;
+    if target.email_verified_at.is_some() {
+        return Err(ApiError::BadRequest("邮箱已验证".into()));
+    }
+
+    let sent = send_account_email(&app_state, &target, EmailTokenPurpose::VerifyEmail)
+        .await
+        .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+    if !sent {
+        return Err(ApiError::BadRequest("发送过于频繁，请稍后再试".into()));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(())))
+}
@@ -1,15 +1,19 @@
 mod anis;
+mod health_checks;
 #[allow(hidden_glob_reexports)]
 mod me;
 mod news;
 mod proxy;
 mod scheduled_tasks;
+mod sessions;
 mod sse;
 mod sync;
 
 pub use anis::*;
+pub use health_checks::*;
 pub use me::*;
 pub use news::*;
 pub use proxy::*;
 pub use scheduled_tasks::*;
+pub use sessions::*;
 pub use sync::*;
@@ -13,7 +13,7 @@ struct NewsSseItem {
     id: i64,
     title: String,
     url: String,
-    /// 新闻分类（复用 source 字段）
+    /// 新闻分类，取 news_source 登记的分类，未登记时沿用来源名称
     category: String,
     /// 新闻来源平台
     news_from: String,
@@ -28,6 +28,7 @@ struct NewsRow {
     title: String,
     url: String,
     source: Option<String>,
+    category: Option<String>,
     published_at: chrono::NaiveDate,
 }
 
@@ -35,9 +36,11 @@ struct NewsRow {
 async fn fetch_initial_news(pool: &PgPool, limit: i64) -> Vec<NewsRow> {
     sqlx::query_as::<_, NewsRow>(
         r#"
-        SELECT id, title, url, source, published_at
-        FROM news_item
-        ORDER BY id DESC
+        SELECT n.id, n.title, n.url, n.source, s.category, n.published_at
+        FROM news_item n
+        LEFT JOIN news_info i ON i.id = n.news_info_id
+        LEFT JOIN news_source s ON s.id = i.news_from
+        ORDER BY n.id DESC
         LIMIT $1
         "#,
     )
@@ -51,10 +54,12 @@ async fn fetch_initial_news(pool: &PgPool, limit: i64) -> Vec<NewsRow> {
 async fn fetch_new_news(pool: &PgPool, after_id: i64, limit: i64) -> Vec<NewsRow> {
     sqlx::query_as::<_, NewsRow>(
         r#"
-        SELECT id, title, url, source, published_at
-        FROM news_item
-        WHERE id > $1
-        ORDER BY id ASC
+        SELECT n.id, n.title, n.url, n.source, s.category, n.published_at
+        FROM news_item n
+        LEFT JOIN news_info i ON i.id = n.news_info_id
+        LEFT JOIN news_source s ON s.id = i.news_from
+        WHERE n.id > $1
+        ORDER BY n.id ASC
         LIMIT $2
         "#,
     )
@@ -72,7 +77,7 @@ fn row_to_sse_bytes(row: &NewsRow) -> Option<web::Bytes> {
         id: row.id,
         title: row.title.clone(),
         url: row.url.clone(),
-        category: source.clone(),
+        category: row.category.clone().unwrap_or_else(|| source.clone()),
         news_from: source,
         news_date: row.published_at.to_string(),
     };
@@ -0,0 +1,54 @@
+use actix_web::{HttpResponse, get};
+use common::api::ApiResponse;
+use common::dto::{TaskCommandDTO, TaskCommandParamDTO};
+use common::po::ApiResult;
+use service::timer_task_command::cmd_registry;
+
+/// 列出可用的任务命令及其参数定义，按命令名排序
+fn list_task_commands() -> Vec<TaskCommandDTO> {
+    let mut commands: Vec<TaskCommandDTO> = cmd_registry()
+        .iter()
+        .map(|(name, command)| TaskCommandDTO {
+            name: name.clone(),
+            description: command.description.to_string(),
+            retryable: command.retryable,
+            params: command
+                .params
+                .iter()
+                .map(|spec| TaskCommandParamDTO {
+                    name: spec.name.to_string(),
+                    kind: spec.kind.as_str().to_string(),
+                    required: spec.required,
+                    description: spec.description.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+#[get("/commands")]
+async fn scheduled_task_commands_get() -> ApiResult {
+    Ok(HttpResponse::Ok().json(ApiResponse::ok(list_task_commands())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::list_task_commands;
+
+    #[test]
+    fn list_task_commands_is_sorted_and_describes_params() {
+        let commands = list_task_commands();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        let health_check = commands.iter().find(|c| c.name == "health_check").unwrap();
+        assert_eq!(health_check.params[0].name, "arg");
+        assert_eq!(health_check.params[0].kind, "url_list");
+        assert!(health_check.params[0].required);
+        assert!(!health_check.retryable);
+    }
+}
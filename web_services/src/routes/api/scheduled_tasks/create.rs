@@ -1,10 +1,14 @@
 use crate::common::AppState;
-use actix_web::{HttpRequest, HttpResponse, post, web};
+use actix_web::{HttpResponse, post, web};
 use common::api::{ApiError, ApiResponse};
 use common::dto::CreateScheduledTaskDTO;
 use common::po::ApiResult;
 use infra::create_scheduled_task;
 use serde::Deserialize;
+use service::timer_task_command::{
+    is_cmd_retryable, validate_cmd_params, validate_cmd_retry_times,
+};
+use timer_tasker::task::{ExecutionOptions, parse_cron, parse_timezone};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,12 +18,18 @@ struct CreateScheduledTaskReq {
     params: serde_json::Value,
     #[serde(default = "bool::default")]
     is_enabled: bool,
-    #[serde(default = "default_retry_times")]
-    retry_times: i32,
+    /// 缺省时支持重试的命令为 3，不支持重试的命令为 0
+    retry_times: Option<i32>,
+    #[serde(default = "default_timezone")]
+    timezone: String,
 }
 
-fn default_retry_times() -> i32 {
-    3
+fn default_retry_times(params: &serde_json::Value) -> u8 {
+    if is_cmd_retryable(params) { 3 } else { 0 }
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
 }
 
 fn parse_retry_times(retry_times: i32) -> Result<u8, ApiError> {
@@ -28,22 +38,33 @@ fn parse_retry_times(retry_times: i32) -> Result<u8, ApiError> {
 }
 
 fn into_create_dto(req: CreateScheduledTaskReq) -> Result<CreateScheduledTaskDTO, ApiError> {
+    parse_cron(&req.cron).map_err(ApiError::BadRequest)?;
+    validate_cmd_params(&req.params).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let options = ExecutionOptions::from_params(&req.params).map_err(ApiError::BadRequest)?;
+    if options.depends_on.contains(&req.name) {
+        return Err(ApiError::BadRequest("depends_on 不能包含任务自身".into()));
+    }
+    let timezone = parse_timezone(&req.timezone).map_err(ApiError::BadRequest)?;
+    let retry_times = match req.retry_times {
+        Some(retry_times) => parse_retry_times(retry_times)?,
+        None => default_retry_times(&req.params),
+    };
+    validate_cmd_retry_times(&req.params, retry_times).map_err(ApiError::BadRequest)?;
     Ok(CreateScheduledTaskDTO {
         name: req.name,
         cron: req.cron,
         params: req.params,
         is_enabled: req.is_enabled,
-        retry_times: parse_retry_times(req.retry_times)?,
+        retry_times,
+        timezone: timezone.name().to_string(),
     })
 }
 
-#[post("/scheduledTasks")]
+#[post("")]
 async fn scheduled_tasks_create(
-    req: HttpRequest,
     body: web::Json<CreateScheduledTaskReq>,
     app_state: web::Data<AppState>,
 ) -> ApiResult {
-    super::ensure_admin_access(&req, &app_state).await?;
     let dto = into_create_dto(body.into_inner())?;
 
     match create_scheduled_task(&dto, &app_state.db_pool).await {
@@ -63,11 +84,80 @@ async fn scheduled_tasks_create(
 
 #[cfg(test)]
 mod tests {
-    use super::parse_retry_times;
+    use super::{CreateScheduledTaskReq, into_create_dto, parse_retry_times};
 
     #[test]
     fn parse_retry_times_rejects_out_of_range_values() {
         assert!(parse_retry_times(-1).is_err());
         assert!(parse_retry_times(256).is_err());
     }
+
+    #[test]
+    fn into_create_dto_rejects_invalid_concurrency_policy() {
+        let req = CreateScheduledTaskReq {
+            name: "task".into(),
+            cron: "0 */5 * * * *".into(),
+            params: serde_json::json!({"cmd": "health_check", "concurrency_policy": "never"}),
+            is_enabled: true,
+            retry_times: None,
+            timezone: "UTC".into(),
+        };
+
+        assert!(into_create_dto(req).is_err());
+    }
+
+    #[test]
+    fn into_create_dto_validates_timezone() {
+        let req = |timezone: &str| CreateScheduledTaskReq {
+            name: "task".into(),
+            cron: "0 */5 * * * *".into(),
+            params: serde_json::json!({"cmd": "health_check", "arg": "https://example.com/health"}),
+            is_enabled: true,
+            retry_times: None,
+            timezone: timezone.into(),
+        };
+
+        assert_eq!(
+            into_create_dto(req(" Asia/Shanghai")).unwrap().timezone,
+            "Asia/Shanghai"
+        );
+        assert!(into_create_dto(req("Asia/Beijing")).is_err());
+    }
+
+    #[test]
+    fn into_create_dto_enforces_command_param_schema() {
+        let req = |params: serde_json::Value| CreateScheduledTaskReq {
+            name: "task".into(),
+            cron: "0 */5 * * * *".into(),
+            params,
+            is_enabled: true,
+            retry_times: None,
+            timezone: "UTC".into(),
+        };
+
+        assert!(into_create_dto(req(serde_json::json!({"cmd": "health_check"}))).is_err());
+        assert!(
+            into_create_dto(req(
+                serde_json::json!({"cmd": "health_check", "arg": "localhost"})
+            ))
+            .is_err()
+        );
+        assert!(into_create_dto(req(serde_json::json!({"cmd": "unknown", "arg": "x"}))).is_err());
+    }
+
+    #[test]
+    fn into_create_dto_rejects_retries_for_non_retryable_command() {
+        let req = |retry_times: Option<i32>| CreateScheduledTaskReq {
+            name: "task".into(),
+            cron: "0 */5 * * * *".into(),
+            params: serde_json::json!({"cmd": "health_check", "arg": "https://example.com/health"}),
+            is_enabled: true,
+            retry_times,
+            timezone: "UTC".into(),
+        };
+
+        assert_eq!(into_create_dto(req(None)).unwrap().retry_times, 0);
+        assert_eq!(into_create_dto(req(Some(0))).unwrap().retry_times, 0);
+        assert!(into_create_dto(req(Some(3))).is_err());
+    }
 }
@@ -5,7 +5,7 @@ use common::api::{ApiError, ApiResponse};
 use common::po::{ApiResult, QueryPage};
 use infra::list_all_scheduled_tasks_by_page;
 
-#[get("/scheduledTasks")]
+#[get("")]
 async fn scheduled_tasks_get(
     _: HttpRequest,
     query: web::Query<QueryPage<TaskFilter>>,
@@ -0,0 +1,151 @@
+use actix_web::{HttpResponse, post, web};
+use chrono::{DateTime, Utc};
+use common::api::ApiResponse;
+use common::dto::{FieldErrorDTO, ScheduledTaskPreviewDTO};
+use common::po::ApiResult;
+use serde::Deserialize;
+use timer_tasker::task::{TaskSchedule, parse_cron, parse_timezone};
+
+const DEFAULT_PREVIEW_COUNT: usize = 5;
+const MAX_PREVIEW_COUNT: usize = 50;
+/// 相邻两次触发间隔小于该值时给出提示
+const FREQUENT_FIRE_SECS: i64 = 60;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewScheduledTaskReq {
+    cron: String,
+    #[serde(default = "default_timezone")]
+    timezone: String,
+    #[serde(default = "default_preview_count")]
+    count: usize,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_preview_count() -> usize {
+    DEFAULT_PREVIEW_COUNT
+}
+
+fn field_error(field: &str, message: impl Into<String>) -> FieldErrorDTO {
+    FieldErrorDTO {
+        field: field.to_string(),
+        message: message.into(),
+    }
+}
+
+/// 校验 cron / timezone / count，全部合法时计算从 now 起的后续触发时间
+fn preview_schedule(req: &PreviewScheduledTaskReq, now: DateTime<Utc>) -> ScheduledTaskPreviewDTO {
+    let mut errors = vec![];
+    let schedule = parse_cron(&req.cron)
+        .map_err(|e| errors.push(field_error("cron", e)))
+        .ok();
+    let timezone = parse_timezone(&req.timezone)
+        .map_err(|e| errors.push(field_error("timezone", e)))
+        .ok();
+    if !(1..=MAX_PREVIEW_COUNT).contains(&req.count) {
+        errors.push(field_error(
+            "count",
+            format!("count 必须在 1-{MAX_PREVIEW_COUNT} 之间"),
+        ));
+    }
+
+    let mut preview = ScheduledTaskPreviewDTO {
+        valid: errors.is_empty(),
+        timezone: req.timezone.trim().to_string(),
+        fire_times: vec![],
+        warnings: vec![],
+        errors,
+    };
+    let (Some(schedule), Some(timezone), true) = (schedule, timezone, preview.valid) else {
+        return preview;
+    };
+
+    let schedule = TaskSchedule::new(schedule, timezone);
+    let upcoming: Vec<DateTime<Utc>> = schedule.after(&now).take(req.count.max(2)).collect();
+    if upcoming.is_empty() {
+        preview
+            .warnings
+            .push("该 cron 表达式之后不会再触发".to_string());
+    }
+    if let [first, second, ..] = upcoming[..] {
+        let gap = (second - first).num_seconds();
+        if gap < FREQUENT_FIRE_SECS {
+            preview
+                .warnings
+                .push(format!("每 {gap} 秒触发一次，请确认第一段是秒而不是分钟"));
+        }
+    }
+    preview.timezone = timezone.name().to_string();
+    preview.fire_times = upcoming
+        .into_iter()
+        .take(req.count)
+        .map(|t| t.with_timezone(&timezone).fixed_offset())
+        .collect();
+    preview
+}
+
+#[post("/preview")]
+async fn scheduled_tasks_preview(body: web::Json<PreviewScheduledTaskReq>) -> ApiResult {
+    let preview = preview_schedule(&body, Utc::now());
+    Ok(HttpResponse::Ok().json(ApiResponse::ok(preview)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PreviewScheduledTaskReq, preview_schedule};
+    use chrono::{TimeZone, Utc};
+
+    fn req(cron: &str, timezone: &str, count: usize) -> PreviewScheduledTaskReq {
+        PreviewScheduledTaskReq {
+            cron: cron.into(),
+            timezone: timezone.into(),
+            count,
+        }
+    }
+
+    #[test]
+    fn preview_lists_fire_times_in_task_timezone() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 17, 55, 0).unwrap();
+
+        let preview = preview_schedule(&req("37 */10 6-23,0-1 * * * *", "Asia/Shanghai", 3), now);
+
+        assert!(preview.valid);
+        assert!(preview.warnings.is_empty());
+        let fire_times: Vec<String> = preview.fire_times.iter().map(|t| t.to_rfc3339()).collect();
+        assert_eq!(
+            fire_times,
+            vec![
+                "2026-01-02T06:00:37+08:00",
+                "2026-01-02T06:10:37+08:00",
+                "2026-01-02T06:20:37+08:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn preview_reports_each_offending_field() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+        let preview = preview_schedule(&req("*/5 * * * *", "Beijing", 0), now);
+
+        assert!(!preview.valid);
+        assert!(preview.fire_times.is_empty());
+        let fields: Vec<&str> = preview.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["cron", "timezone", "count"]);
+    }
+
+    #[test]
+    fn preview_warns_when_firing_every_few_seconds() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+        let preview = preview_schedule(&req("* */5 * * * *", "UTC", 1), now);
+
+        assert!(preview.valid);
+        assert_eq!(preview.fire_times.len(), 1);
+        assert_eq!(preview.warnings.len(), 1);
+        assert!(preview.warnings[0].contains("每 1 秒"));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::common::AppState;
+use actix_web::{HttpResponse, post, web};
+use common::api::{ApiError, ApiResponse};
+use common::po::ApiResult;
+use infra::{get_scheduled_task_by_id, get_scheduled_task_run};
+
+#[post("/{id}/run")]
+async fn scheduled_task_run_now(path: web::Path<i64>, app_state: web::Data<AppState>) -> ApiResult {
+    let id = path.into_inner();
+
+    let task = get_scheduled_task_by_id(id, &app_state.db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("查询定时任务 {id} 失败: {e:?}");
+            ApiError::Database("数据库查询失败".into())
+        })?
+        .ok_or_else(|| ApiError::NotFound(format!("任务 {id} 不存在")))?;
+
+    let run_id = app_state.task_manager.run_task_now(&task).await?;
+
+    match get_scheduled_task_run(id, run_id, &app_state.db_pool).await {
+        Ok(Some(run)) => Ok(HttpResponse::Accepted().json(ApiResponse::ok(run))),
+        Ok(None) => Err(ApiError::NotFound(format!("执行记录 {run_id} 不存在"))),
+        Err(e) => {
+            tracing::error!("查询任务 {id} 执行记录 {run_id} 失败: {e:?}");
+            Err(ApiError::Database("数据库查询失败".into()))
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::common::AppState;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use common::ScheduledTaskRunFilter;
+use common::api::{ApiError, ApiResponse};
+use common::po::{ApiResult, QueryPage};
+use infra::{get_scheduled_task_run, list_scheduled_task_runs_by_page};
+
+#[get("/{id}/runs")]
+async fn scheduled_task_runs_get(
+    _: HttpRequest,
+    path: web::Path<i64>,
+    query: web::Query<QueryPage<ScheduledTaskRunFilter>>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let id = path.into_inner();
+    match list_scheduled_task_runs_by_page(id, query, &app_state.db_pool).await {
+        Ok(runs) => Ok(HttpResponse::Ok().json(ApiResponse::ok(runs))),
+        Err(e) => {
+            tracing::error!("查询任务 {id} 执行记录失败: {e:?}");
+            Err(ApiError::Database("数据库查询失败".into()))
+        }
+    }
+}
+
+#[get("/{id}/runs/{run_id}")]
+async fn scheduled_task_run_get(
+    _: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let (id, run_id) = path.into_inner();
+    match get_scheduled_task_run(id, run_id, &app_state.db_pool).await {
+        Ok(Some(run)) => Ok(HttpResponse::Ok().json(ApiResponse::ok(run))),
+        Ok(None) => Err(ApiError::NotFound(format!("执行记录 {run_id} 不存在"))),
+        Err(e) => {
+            tracing::error!("查询任务 {id} 执行记录 {run_id} 失败: {e:?}");
+            Err(ApiError::Database("数据库查询失败".into()))
+        }
+    }
+}
@@ -1,18 +1,16 @@
 use crate::common::AppState;
-use actix_web::{HttpRequest, HttpResponse, patch, web};
+use actix_web::{HttpResponse, patch, web};
 use common::api::{ApiError, ApiResponse};
 use common::dto::ToggleScheduledTaskDTO;
 use common::po::ApiResult;
 use infra::toggle_scheduled_task;
 
-#[patch("/scheduledTasks/{id}/status")]
+#[patch("/{id}/status")]
 async fn scheduled_tasks_toggle(
-    req: HttpRequest,
     path: web::Path<i64>,
     body: web::Json<ToggleScheduledTaskDTO>,
     app_state: web::Data<AppState>,
 ) -> ApiResult {
-    super::ensure_admin_access(&req, &app_state).await?;
     let id = path.into_inner();
     match toggle_scheduled_task(id, &body, &app_state.db_pool).await {
         Ok(()) => {
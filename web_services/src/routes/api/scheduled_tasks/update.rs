@@ -1,10 +1,14 @@
 use crate::common::AppState;
-use actix_web::{HttpRequest, HttpResponse, put, web};
+use actix_web::{HttpResponse, put, web};
 use common::api::{ApiError, ApiResponse};
 use common::dto::UpdateScheduledTaskDTO;
 use common::po::ApiResult;
-use infra::update_scheduled_task;
+use infra::{get_scheduled_task_by_id, update_scheduled_task};
 use serde::Deserialize;
+use service::timer_task_command::{
+    is_cmd_retryable, validate_cmd_params, validate_cmd_retry_times,
+};
+use timer_tasker::task::{ExecutionOptions, parse_cron, parse_timezone};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,6 +17,7 @@ struct UpdateScheduledTaskReq {
     cron: Option<String>,
     params: Option<serde_json::Value>,
     retry_times: Option<i32>,
+    timezone: Option<String>,
 }
 
 fn parse_retry_times(retry_times: i32) -> Result<u8, ApiError> {
@@ -21,24 +26,62 @@ fn parse_retry_times(retry_times: i32) -> Result<u8, ApiError> {
 }
 
 fn into_update_dto(req: UpdateScheduledTaskReq) -> Result<UpdateScheduledTaskDTO, ApiError> {
+    if let Some(cron) = &req.cron {
+        parse_cron(cron).map_err(ApiError::BadRequest)?;
+    }
+    if let Some(params) = &req.params {
+        validate_cmd_params(params).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+        ExecutionOptions::from_params(params).map_err(ApiError::BadRequest)?;
+    }
     Ok(UpdateScheduledTaskDTO {
         name: req.name,
         cron: req.cron,
         params: req.params,
         retry_times: req.retry_times.map(parse_retry_times).transpose()?,
+        timezone: req
+            .timezone
+            .map(|tz| parse_timezone(&tz).map(|tz| tz.name().to_string()))
+            .transpose()
+            .map_err(ApiError::BadRequest)?,
     })
 }
 
-#[put("/scheduledTasks/{id}")]
+/// 结合任务当前配置确定更新后的 retry_times：显式为不支持重试的命令设置重试时报错，
+/// 只把命令改为不支持重试的命令时把原有的重试次数归零
+fn resolve_retry_times(
+    params: &serde_json::Value,
+    requested: Option<u8>,
+    current: u8,
+) -> Result<Option<u8>, ApiError> {
+    match requested {
+        Some(retry_times) => {
+            validate_cmd_retry_times(params, retry_times).map_err(ApiError::BadRequest)?;
+            Ok(Some(retry_times))
+        }
+        None if current > 0 && !is_cmd_retryable(params) => Ok(Some(0)),
+        None => Ok(None),
+    }
+}
+
+#[put("/{id}")]
 async fn scheduled_tasks_update(
-    req: HttpRequest,
     path: web::Path<i64>,
     body: web::Json<UpdateScheduledTaskReq>,
     app_state: web::Data<AppState>,
 ) -> ApiResult {
-    super::ensure_admin_access(&req, &app_state).await?;
     let id = path.into_inner();
-    let dto = into_update_dto(body.into_inner())?;
+    let mut dto = into_update_dto(body.into_inner())?;
+    if dto.params.is_some() || dto.retry_times.is_some() {
+        let current = get_scheduled_task_by_id(id, &app_state.db_pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("查询定时任务 {id} 失败: {e:?}");
+                ApiError::Internal("查询定时任务失败".into())
+            })?
+            .ok_or_else(|| ApiError::NotFound(format!("任务 {id} 不存在或更新失败")))?;
+        let params = dto.params.as_ref().unwrap_or(&current.params);
+        dto.retry_times = resolve_retry_times(params, dto.retry_times, current.retry_times)?;
+    }
 
     match update_scheduled_task(id, &dto, &app_state.db_pool).await {
         Ok(task) => {
@@ -57,7 +100,8 @@ async fn scheduled_tasks_update(
 
 #[cfg(test)]
 mod tests {
-    use super::{UpdateScheduledTaskReq, into_update_dto};
+    use super::{UpdateScheduledTaskReq, into_update_dto, resolve_retry_times};
+    use serde_json::json;
 
     #[test]
     fn into_update_dto_rejects_out_of_range_retry_times() {
@@ -66,8 +110,55 @@ mod tests {
             cron: None,
             params: None,
             retry_times: Some(256),
+            timezone: None,
+        };
+
+        assert!(into_update_dto(req).is_err());
+    }
+
+    #[test]
+    fn into_update_dto_rejects_unknown_timezone() {
+        let req = UpdateScheduledTaskReq {
+            name: None,
+            cron: None,
+            params: None,
+            retry_times: None,
+            timezone: Some("GMT+8".into()),
         };
 
         assert!(into_update_dto(req).is_err());
     }
+
+    #[test]
+    fn into_update_dto_rejects_cron_without_seconds() {
+        let req = UpdateScheduledTaskReq {
+            name: None,
+            cron: Some("*/5 * * * *".into()),
+            params: None,
+            retry_times: None,
+            timezone: None,
+        };
+
+        assert!(into_update_dto(req).is_err());
+    }
+
+    #[test]
+    fn resolve_retry_times_rejects_retries_for_non_retryable_command() {
+        let health_check = json!({"cmd": "health_check", "arg": "https://example.com/health"});
+        let fetch = json!({"cmd": "fetch_all_news", "arg": "https://news.likanug.top"});
+
+        assert!(resolve_retry_times(&health_check, Some(2), 0).is_err());
+        assert_eq!(
+            resolve_retry_times(&health_check, Some(0), 3).unwrap(),
+            Some(0)
+        );
+        // 改为健康检测时原有的重试次数归零
+        assert_eq!(
+            resolve_retry_times(&health_check, None, 3).unwrap(),
+            Some(0)
+        );
+        assert_eq!(resolve_retry_times(&health_check, None, 0).unwrap(), None);
+        assert_eq!(resolve_retry_times(&fetch, Some(5), 0).unwrap(), Some(5));
+        assert_eq!(resolve_retry_times(&fetch, None, 3).unwrap(), None);
+    }
 }
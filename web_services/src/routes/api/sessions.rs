@@ -0,0 +1,63 @@
+use crate::common::{AppState, ExtractToken};
+use crate::routes::login::clear_auth_cookies;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, delete, get, web};
+use common::api::{ApiError, ApiResponse};
+use common::po::ApiResult;
+use common::utils::JwtClaims;
+use infra::{bump_user_token_version, list_user_sessions, revoke_user_session};
+
+fn current_claims(req: &HttpRequest) -> Result<JwtClaims, ApiError> {
+    req.extensions()
+        .get::<JwtClaims>()
+        .cloned()
+        .ok_or_else(|| ApiError::Unauthorized("未携带或非法的 JWT".into()))
+}
+
+/// 当前用户仍然有效的登录会话，current 标记发起本次请求的会话
+#[get("/me/sessions")]
+async fn me_sessions_get(req: HttpRequest, app_state: web::Data<AppState>) -> ApiResult {
+    let claims = current_claims(&req)?;
+    let refresh_token = req.get_refresh_token();
+
+    let sessions = list_user_sessions(claims.uid, refresh_token.as_deref(), &app_state.db_pool)
+        .await
+        .map_err(|_| ApiError::Internal("查询登录会话失败".into()))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::ok(sessions)))
+}
+
+/// 吊销指定会话，该会话的 refresh_token 失效，已签发的 access_token 到期后不再续期
+#[delete("/me/sessions/{id}")]
+async fn me_session_delete(
+    req: HttpRequest,
+    path: web::Path<i64>,
+    app_state: web::Data<AppState>,
+) -> ApiResult {
+    let claims = current_claims(&req)?;
+    let session_id = path.into_inner();
+
+    match revoke_user_session(claims.uid, session_id, &app_state.db_pool).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(()))),
+        Ok(false) => Err(ApiError::NotFound(format!("会话 {session_id} 不存在"))),
+        Err(_) => Err(ApiError::Internal("吊销登录会话失败".into())),
+    }
+}
+
+/// 退出所有设备：递增 token 版本，吊销全部 refresh_token 并清除当前 Cookie
+#[delete("/me/sessions")]
+async fn me_sessions_delete(req: HttpRequest, app_state: web::Data<AppState>) -> ApiResult {
+    let claims = current_claims(&req)?;
+
+    match bump_user_token_version(claims.uid, &app_state.db_pool).await {
+        Ok(Some(token_version)) => {
+            app_state.user_auth_cache.invalidate(claims.uid);
+            tracing::info!(
+                "用户 {} 已退出所有设备，token 版本递增至 {token_version}",
+                claims.uid
+            );
+            Ok(clear_auth_cookies(app_state.configuration.is_production))
+        }
+        Ok(None) => Err(ApiError::Unauthorized("用户不存在".into())),
+        Err(_) => Err(ApiError::Internal("退出所有设备失败".into())),
+    }
+}
@@ -1,11 +1,11 @@
 use crate::common::AppState;
-use actix_web::{HttpRequest, HttpResponse, post, web};
+use actix_web::{HttpResponse, post, web};
 use common::api::{ApiError, ApiResponse};
 use common::po::ApiResult;
-use cron::Schedule;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::str::FromStr;
+use service::timer_task_command::{validate_cmd_params, validate_cmd_retry_times};
+use timer_tasker::task::{ExecutionOptions, parse_cron, parse_timezone};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskReq {
@@ -13,12 +13,17 @@ pub struct TaskReq {
     pub cron: String,
     pub params: Value,
     pub retry_times: i32,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
 }
 
-fn parse_retry_times(retry_times: i32) -> Result<i16, ApiError> {
-    let retry_times = u8::try_from(retry_times)
-        .map_err(|_| ApiError::BadRequest("retryTimes 超出范围，必须在 0-255 之间".into()))?;
-    Ok(i16::from(retry_times))
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn parse_retry_times(retry_times: i32) -> Result<u8, ApiError> {
+    u8::try_from(retry_times)
+        .map_err(|_| ApiError::BadRequest("retryTimes 超出范围，必须在 0-255 之间".into()))
 }
 
 fn validate_task_req(req: &TaskReq) -> Result<(), ApiError> {
@@ -30,46 +35,46 @@ fn validate_task_req(req: &TaskReq) -> Result<(), ApiError> {
         return Err(ApiError::BadRequest("cron 不能为空".into()));
     }
 
-    Schedule::from_str(req.cron.trim())
-        .map_err(|_| ApiError::BadRequest("cron 表达式不合法".into()))?;
+    parse_cron(&req.cron).map_err(ApiError::BadRequest)?;
+
+    validate_cmd_params(&req.params).map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
-    let cmd = req
-        .params
-        .get("cmd")
-        .and_then(Value::as_str)
-        .map(str::trim)
-        .unwrap_or("");
-    if cmd.is_empty() {
-        return Err(ApiError::BadRequest("params.cmd 不能为空".into()));
+    let options = ExecutionOptions::from_params(&req.params).map_err(ApiError::BadRequest)?;
+    if options
+        .depends_on
+        .iter()
+        .any(|name| name == req.name.trim())
+    {
+        return Err(ApiError::BadRequest("depends_on 不能包含任务自身".into()));
     }
+    parse_timezone(&req.timezone).map_err(ApiError::BadRequest)?;
+    let retry_times = parse_retry_times(req.retry_times)?;
+    validate_cmd_retry_times(&req.params, retry_times).map_err(ApiError::BadRequest)?;
 
     Ok(())
 }
 
-#[post("/sync/task_source")]
-async fn sync_task_source(
-    http_req: HttpRequest,
-    req: web::Json<TaskReq>,
-    app_state: web::Data<AppState>,
-) -> ApiResult {
-    crate::routes::api::scheduled_tasks::ensure_admin_access(&http_req, &app_state).await?;
+#[post("")]
+async fn sync_task_source(req: web::Json<TaskReq>, app_state: web::Data<AppState>) -> ApiResult {
     validate_task_req(&req)?;
     let retry_times = parse_retry_times(req.retry_times)?;
 
     sqlx::query(
         r#"
-        INSERT INTO scheduled_tasks  (name, cron, params, retry_times)
-        values($1, $2, $3, $4)
+        INSERT INTO scheduled_tasks  (name, cron, params, retry_times, timezone)
+        values($1, $2, $3, $4, $5)
         ON CONFLICT (name) DO UPDATE SET
             cron = EXCLUDED.cron,
             params = EXCLUDED.params,
-            retry_times = EXCLUDED.retry_times
+            retry_times = EXCLUDED.retry_times,
+            timezone = EXCLUDED.timezone
         "#,
     )
     .bind(req.name.clone())
     .bind(req.cron.clone())
     .bind(req.params.clone())
-    .bind(retry_times)
+    .bind(i16::from(retry_times))
+    .bind(req.timezone.trim())
     .execute(&app_state.db_pool)
     .await
     .map_err(|e| {
@@ -95,11 +100,23 @@ mod tests {
         TaskReq {
             name: "news_task".to_string(),
             cron: "0 */5 * * * * *".to_string(),
-            params: serde_json::json!({"arg": "x", "cmd": "sync_news"}),
-            retry_times: 3,
+            params: serde_json::json!({"arg": "https://example.com/health", "cmd": "health_check"}),
+            retry_times: 0,
+            timezone: "UTC".to_string(),
         }
     }
 
+    #[test]
+    fn validate_task_req_rejects_retries_for_non_retryable_command() {
+        let mut req = sample_req();
+        req.retry_times = 3;
+        assert!(validate_task_req(&req).is_err());
+
+        req.params =
+            serde_json::json!({"arg": "https://news.likanug.top", "cmd": "fetch_all_news"});
+        assert!(validate_task_req(&req).is_ok());
+    }
+
     #[test]
     fn parse_retry_times_accepts_u8_range() {
         assert_eq!(parse_retry_times(0).unwrap(), 0);
@@ -159,4 +176,38 @@ mod tests {
         req.params = serde_json::json!({"arg": "x", "cmd": 123});
         assert!(validate_task_req(&req).is_err());
     }
+
+    #[test]
+    fn validate_task_req_rejects_invalid_timeout_secs() {
+        let mut req = sample_req();
+        req.params = serde_json::json!({"arg": "https://example.com/health", "cmd": "health_check", "timeout_secs": -5});
+        assert!(validate_task_req(&req).is_err());
+    }
+
+    #[test]
+    fn validate_task_req_rejects_unknown_timezone() {
+        let mut req = sample_req();
+        req.timezone = "China/Beijing".to_string();
+        assert!(validate_task_req(&req).is_err());
+    }
+
+    #[test]
+    fn validate_task_req_rejects_self_dependency() {
+        let mut req = sample_req();
+        req.params = serde_json::json!({"arg": "https://example.com/health", "cmd": "health_check", "depends_on": ["news_task"]});
+        assert!(validate_task_req(&req).is_err());
+
+        req.params = serde_json::json!({"arg": "https://example.com/health", "cmd": "health_check", "depends_on": ["fetch_news"]});
+        assert!(validate_task_req(&req).is_ok());
+    }
+
+    #[test]
+    fn validate_task_req_rejects_unknown_cmd_and_invalid_arg() {
+        let mut req = sample_req();
+        req.params = serde_json::json!({"arg": "x", "cmd": "sync_news"});
+        assert!(validate_task_req(&req).is_err());
+
+        req.params = serde_json::json!({"arg": "not a url", "cmd": "fetch_all_news"});
+        assert!(validate_task_req(&req).is_err());
+    }
 }
@@ -0,0 +1,32 @@
+use crate::common::{AppState, hash_email_token};
+use actix_web::{HttpResponse, post, web};
+use common::api::{ApiError, ApiResponse};
+use common::po::ApiResult;
+use infra::verify_email_with_token;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+///
+/// 用邮件中的 token 完成邮箱验证 <br>
+/// /auth/email/verify  POST请求
+///
+#[post("/auth/email/verify")]
+async fn auth_email_verify(
+    app_state: web::Data<AppState>,
+    body: web::Json<VerifyEmailRequest>,
+) -> ApiResult {
+    let user_id = verify_email_with_token(&hash_email_token(body.token.trim()), &app_state.db_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("验证邮箱失败: {e:?}");
+            ApiError::Internal("服务器内部错误".into())
+        })?
+        .ok_or_else(|| ApiError::InvalidData("验证链接无效或已过期".into()))?;
+
+    tracing::info!("用户 {user_id} 已验证邮箱");
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(())))
+}
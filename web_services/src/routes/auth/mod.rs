@@ -1,5 +1,9 @@
+mod email_verification;
 mod oauth;
+mod password;
 mod token;
 
+pub use email_verification::*;
 pub use oauth::*;
+pub use password::*;
 pub use token::*;
@@ -0,0 +1,78 @@
+use crate::common::{AppState, hash_email_token, send_account_email};
+use actix_web::{HttpResponse, post, web};
+use common::api::{ApiError, ApiResponse};
+use common::po::{ApiResult, EmailTokenPurpose};
+use infra::{find_user_email_target, reset_password_with_token};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub password: String,
+}
+
+///
+/// 找回密码：向邮箱发送重置密码链接 <br>
+/// 无论邮箱是否已注册都返回成功，避免通过该接口探测账户
+///
+#[post("/auth/password/forgot")]
+async fn auth_password_forgot(
+    app_state: web::Data<AppState>,
+    body: web::Json<ForgotPasswordRequest>,
+) -> ApiResult {
+    let email = body.email.trim();
+    if email.is_empty() {
+        return Err(ApiError::InvalidData("请提供 email".into()));
+    }
+
+    let target = find_user_email_target(None, Some(email), &app_state.db_pool)
+        .await
+        .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+    if let Some(target) = target {
+        send_account_email(&app_state, &target, EmailTokenPurpose::ResetPassword)
+            .await
+            .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(())))
+}
+
+///
+/// 用邮件中的 token 设置新密码 <br>
+/// 成功后该用户所有设备上的登录状态全部失效
+///
+#[post("/auth/password/reset")]
+async fn auth_password_reset(
+    app_state: web::Data<AppState>,
+    body: web::Json<ResetPasswordRequest>,
+) -> ApiResult {
+    if body.password.len() < 8 {
+        return Err(ApiError::InvalidData("密码长度不能少于 8 位".into()));
+    }
+
+    let password_hash = bcrypt::hash(&body.password, bcrypt::DEFAULT_COST).map_err(|e| {
+        tracing::error!("密码哈希失败: {e}");
+        ApiError::Internal("服务器内部错误".into())
+    })?;
+
+    let user_id = reset_password_with_token(
+        &hash_email_token(body.token.trim()),
+        &password_hash,
+        &app_state.db_pool,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("重置密码失败: {e:?}");
+        ApiError::Internal("服务器内部错误".into())
+    })?
+    .ok_or_else(|| ApiError::InvalidData("重置链接无效或已过期".into()))?;
+
+    app_state.user_auth_cache.invalidate(user_id);
+    tracing::info!("用户 {user_id} 已通过邮件重置密码");
+    Ok(HttpResponse::Ok().json(ApiResponse::<()>::ok(())))
+}
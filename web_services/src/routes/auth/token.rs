@@ -1,4 +1,4 @@
-use crate::common::AppState;
+use crate::common::{AppState, ClientInfo};
 use actix_web::cookie::Cookie;
 use actix_web::{HttpRequest, HttpResponse, post, web};
 use chrono::Utc;
@@ -33,14 +33,43 @@ struct UserWithIdentity {
     avatar_url: Option<String>,
     provider: Option<String>,
     provider_uid: Option<String>,
+    token_version: i64,
+    status: String,
 }
 
 #[derive(Debug, Serialize, FromRow)]
 struct RefreshTokens {
     user_id: i64,
+    family_id: i64,
+    revoked: bool,
+    rotated_at: Option<chrono::DateTime<Utc>>,
+    expires_at: chrono::DateTime<Utc>,
     session_expires_at: chrono::DateTime<Utc>,
 }
 
+/// 旧 refresh_token 的核对结果
+#[derive(Debug, PartialEq)]
+enum RefreshCheck {
+    Valid,
+    /// 已被刷新轮换的 token 再次出现，视为被盗用
+    Reused,
+    /// 退出登录、吊销会话等主动吊销的 token
+    Revoked,
+    Expired,
+}
+
+fn check_refresh_token(rec: &RefreshTokens, now: chrono::DateTime<Utc>) -> RefreshCheck {
+    if rec.rotated_at.is_some() {
+        RefreshCheck::Reused
+    } else if rec.revoked {
+        RefreshCheck::Revoked
+    } else if rec.expires_at <= now || rec.session_expires_at <= now {
+        RefreshCheck::Expired
+    } else {
+        RefreshCheck::Valid
+    }
+}
+
 ///
 /// 刷新access_token的API，cookie中携带refresh token获取access_token <br>
 /// /auth/token/refresh  POST请求
@@ -61,12 +90,9 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
     // 1️⃣ 校验并消费旧 refresh_token
     let rec = sqlx::query_as::<_, RefreshTokens>(
         r#"
-            SELECT user_id, session_expires_at
+            SELECT user_id, family_id, revoked, rotated_at, expires_at, session_expires_at
             FROM refresh_tokens
             WHERE token = $1
-              AND revoked = false
-              AND expires_at > now()
-              AND session_expires_at > now()
             FOR UPDATE
         "#,
     )
@@ -86,14 +112,50 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
         }
     };
 
+    match check_refresh_token(&rec, Utc::now()) {
+        RefreshCheck::Valid => {}
+        RefreshCheck::Revoked | RefreshCheck::Expired => {
+            tx.rollback().await.ok();
+            return Err(ApiError::Unauthorized("refresh token 无效或已过期".into()));
+        }
+        RefreshCheck::Reused => {
+            // 已轮换的 token 被重放：吊销整个会话，合法持有者也需要重新登录
+            sqlx::query(
+                r#"
+                    UPDATE refresh_tokens
+                    SET revoked = true
+                    WHERE family_id = $1
+                      AND revoked = false
+                "#,
+            )
+            .bind(rec.family_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                tracing::error!("revoke refresh_token family failed: {e}");
+                ApiError::Internal("服务器错误".into())
+            })?;
+            tx.commit().await.map_err(|e| {
+                tracing::error!("commit tx failed: {e}");
+                ApiError::Internal("服务器错误".into())
+            })?;
+            tracing::warn!(
+                "检测到 refresh_token 重放，已吊销用户 {} 的会话 {}",
+                rec.user_id,
+                rec.family_id
+            );
+            return Err(ApiError::Unauthorized("refresh token 无效或已过期".into()));
+        }
+    }
+
     let user_id = rec.user_id;
     let session_expires_at = rec.session_expires_at;
 
-    // 2️⃣ 立即吊销旧 token（防并发重放）
+    // 2️⃣ 立即吊销旧 token 并记录轮换（防并发重放）
     sqlx::query(
         r#"
             UPDATE refresh_tokens
-            SET revoked = true
+            SET revoked = true, rotated_at = NOW()
             WHERE token = $1
         "#,
     )
@@ -119,18 +181,23 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
         ApiError::Internal("refresh_token 生成失败".into())
     })?;
 
+    // 新 token 沿用旧 token 的 family_id，归属同一会话
+    let client = ClientInfo::from_request(&req);
     sqlx::query(
         r#"
             INSERT INTO refresh_tokens
-                (user_id, token, expires_at, session_expires_at)
+                (user_id, token, expires_at, session_expires_at, family_id, user_agent, ip_address)
             VALUES
-                ($1, $2, $3, $4)
+                ($1, $2, $3, $4, $5, $6, $7)
         "#,
     )
     .bind(user_id)
     .bind(new_refresh_token.clone().token)
     .bind(new_expires_at)
     .bind(session_expires_at)
+    .bind(rec.family_id)
+    .bind(client.user_agent)
+    .bind(client.ip_address)
     .execute(&mut *tx)
     .await
     .map_err(|e| {
@@ -148,7 +215,9 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
                     ui.display_name,
                     ui.avatar_url,
                     uident.provider,
-                    uident.provider_uid
+                    uident.provider_uid,
+                    ui.token_version,
+                    ui.status
                 FROM user_info ui
                 LEFT JOIN user_identities uident ON uident.user_id = ui.id
                 WHERE ui.id = $1
@@ -162,6 +231,11 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
         ApiError::Internal("服务器错误".into())
     })?;
 
+    if user.status != "active" {
+        tx.rollback().await.ok();
+        return Err(ApiError::Forbidden("账号不可用".into()));
+    }
+
     let roles: Vec<String> = sqlx::query_scalar(
         r#"
             SELECT r.name
@@ -198,7 +272,7 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
         email: user.email,
         roles,
         r#type: user.provider.unwrap_or_default(),
-        ver: 0,
+        ver: user.token_version,
         avatar_url: user.avatar_url,
     };
 
@@ -232,3 +306,76 @@ async fn auth_token_refresh(req: HttpRequest, app_state: web::Data<AppState>) ->
             "user": common_user
         }))))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn token(revoked: bool, expires_in: Duration, session_expires_in: Duration) -> RefreshTokens {
+        let now = Utc::now();
+        RefreshTokens {
+            user_id: 1,
+            family_id: 7,
+            revoked,
+            rotated_at: None,
+            expires_at: now + expires_in,
+            session_expires_at: now + session_expires_in,
+        }
+    }
+
+    #[test]
+    fn check_refresh_token_accepts_live_token() {
+        let rec = token(false, Duration::days(1), Duration::days(30));
+        assert_eq!(check_refresh_token(&rec, Utc::now()), RefreshCheck::Valid);
+    }
+
+    #[test]
+    fn check_refresh_token_rejects_expired_token_or_session() {
+        let expired = token(false, Duration::days(-1), Duration::days(30));
+        let session_ended = token(false, Duration::days(1), Duration::days(-1));
+
+        assert_eq!(
+            check_refresh_token(&expired, Utc::now()),
+            RefreshCheck::Expired
+        );
+        assert_eq!(
+            check_refresh_token(&session_ended, Utc::now()),
+            RefreshCheck::Expired
+        );
+    }
+
+    fn rotated(expires_in: Duration) -> RefreshTokens {
+        RefreshTokens {
+            rotated_at: Some(Utc::now() - Duration::minutes(5)),
+            ..token(true, expires_in, Duration::days(30))
+        }
+    }
+
+    #[test]
+    fn check_refresh_token_treats_only_rotated_token_as_reuse() {
+        let rotated_live = rotated(Duration::days(1));
+        let rotated_and_expired = rotated(Duration::days(-1));
+        // 退出登录、吊销会话、token_version 变更、重置密码只置 revoked
+        let revoked = token(true, Duration::days(1), Duration::days(30));
+        let revoked_and_expired = token(true, Duration::days(-1), Duration::days(30));
+
+        assert_eq!(
+            check_refresh_token(&revoked, Utc::now()),
+            RefreshCheck::Revoked
+        );
+        assert_eq!(
+            check_refresh_token(&revoked_and_expired, Utc::now()),
+            RefreshCheck::Revoked
+        );
+
+        assert_eq!(
+            check_refresh_token(&rotated_live, Utc::now()),
+            RefreshCheck::Reused
+        );
+        assert_eq!(
+            check_refresh_token(&rotated_and_expired, Utc::now()),
+            RefreshCheck::Reused
+        );
+    }
+}
@@ -1,13 +1,22 @@
-use crate::common::{AppState, ExtractToken};
+use crate::common::{AppState, ClientInfo, ExtractToken};
 use actix_web::cookie::{Cookie, SameSite};
 use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
 use chrono::Utc;
 use common::api::{ApiError, ApiResponse};
 use common::utils::{CommonUser, generate_jwt, generate_refresh_token};
 use common::{ACCESS_TOKEN, REFRESH_TOKEN};
+use infra::{record_login_failure, reset_login_failures};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+/// 登录失败的统一提示，不区分账户不存在、密码错误和账户被锁定
+const LOGIN_FAILED_MSG: &str = "用户名/邮箱或密码错误，或账号已被临时锁定";
+
+/// 账户不存在或没有设置密码时用于比对的哈希，使各种失败情况的耗时一致
+static DUMMY_PASSWORD_HASH: Lazy<String> =
+    Lazy::new(|| bcrypt::hash("agora-dummy-password", bcrypt::DEFAULT_COST).unwrap_or_default());
+
 fn token_window_days(
     app_state: &web::Data<AppState>,
     token_key: &'static str,
@@ -33,16 +42,23 @@ pub struct LoginRequest {
 #[derive(Debug, Serialize, FromRow)]
 struct LoginUser {
     id: i64,
-    email: String,
+    email: Option<String>,
     username: String,
-    password: String,
+    password: Option<String>,
     avatar_url: Option<String>,
     token_version: i64,
     status: String,
+    locked_until: Option<chrono::DateTime<Utc>>,
+    failed_login_attempts: Option<i32>,
+}
+
+fn is_locked(locked_until: Option<chrono::DateTime<Utc>>, now: chrono::DateTime<Utc>) -> bool {
+    locked_until.is_some_and(|until| until > now)
 }
 
 #[post("/login")]
 async fn login(
+    http_req: HttpRequest,
     app_state: web::Data<AppState>,
     body: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, ApiError> {
@@ -68,7 +84,8 @@ async fn login(
 
     let user = sqlx::query_as::<_, LoginUser>(
         r#"
-            SELECT id, email, username, password, avatar_url, token_version, status
+            SELECT id, email, username, password, avatar_url, token_version, status,
+                   locked_until, failed_login_attempts
             FROM user_info
             WHERE ($1::text IS NULL OR username = $1)
               AND ($2::text IS NULL OR email = $2)
@@ -83,20 +100,47 @@ async fn login(
     .map_err(|e| {
         tracing::error!("查询登录用户失败: {e}");
         ApiError::Internal("服务器内部错误".into())
-    })?
-    .ok_or_else(|| ApiError::Unauthorized("用户名/邮箱或密码错误".into()))?;
+    })?;
 
-    if user.status != "active" {
-        return Err(ApiError::Forbidden("账号不可用".into()));
+    // 账户不存在、没有密码或已锁定时也做一次哈希比对，避免通过耗时判断账户是否存在
+    let password_hash = user
+        .as_ref()
+        .and_then(|u| u.password.as_deref())
+        .unwrap_or(DUMMY_PASSWORD_HASH.as_str());
+    let verify_ok = bcrypt::verify(&req.password, password_hash).unwrap_or(false);
+
+    let Some(user) = user else {
+        return Err(ApiError::Unauthorized(LOGIN_FAILED_MSG.into()));
+    };
+
+    if is_locked(user.locked_until, Utc::now()) {
+        return Err(ApiError::Unauthorized(LOGIN_FAILED_MSG.into()));
     }
 
-    let verify_ok = bcrypt::verify(&req.password, &user.password).map_err(|e| {
-        tracing::error!("校验密码失败: {e}");
-        ApiError::Internal("服务器内部错误".into())
-    })?;
+    if !verify_ok || user.password.is_none() {
+        let lockout = &app_state.configuration.login_lockout;
+        let locked_until = record_login_failure(
+            user.id,
+            lockout.max_failed_attempts,
+            lockout.lockout_minutes,
+            &app_state.db_pool,
+        )
+        .await
+        .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+        if let Some(until) = locked_until {
+            tracing::warn!("用户 {} 连续登录失败，锁定至 {until}", user.id);
+        }
+        return Err(ApiError::Unauthorized(LOGIN_FAILED_MSG.into()));
+    }
 
-    if !verify_ok {
-        return Err(ApiError::Unauthorized("用户名/邮箱或密码错误".into()));
+    if user.failed_login_attempts.unwrap_or(0) > 0 || user.locked_until.is_some() {
+        reset_login_failures(user.id, &app_state.db_pool)
+            .await
+            .map_err(|_| ApiError::Internal("服务器内部错误".into()))?;
+    }
+
+    if user.status != "active" {
+        return Err(ApiError::Forbidden("账号不可用".into()));
     }
 
     let roles: Vec<String> = sqlx::query_scalar(
@@ -122,7 +166,7 @@ async fn login(
         id: user.id,
         sub: user.username.clone(),
         uid: user.id,
-        email: Some(user.email.clone()),
+        email: user.email.clone(),
         avatar_url: user.avatar_url,
         r#type: "local".to_string(),
         roles,
@@ -140,17 +184,22 @@ async fn login(
     })?;
 
     let session_expires_at = Utc::now() + chrono::Duration::days(30);
+    let client = ClientInfo::from_request(&http_req);
 
+    // 每次登录开启新的会话，family_id 由序列生成
     sqlx::query(
         r#"
-            INSERT INTO refresh_tokens (user_id, token, expires_at, session_expires_at)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO refresh_tokens
+                (user_id, token, expires_at, session_expires_at, user_agent, ip_address)
+            VALUES ($1, $2, $3, $4, $5, $6)
         "#,
     )
     .bind(user.id)
     .bind(&refresh_token.token)
     .bind(refresh_token.expires_at)
     .bind(session_expires_at)
+    .bind(client.user_agent)
+    .bind(client.ip_address)
     .execute(&app_state.db_pool)
     .await
     .map_err(|e| {
@@ -200,8 +249,12 @@ async fn logout(app_state: web::Data<AppState>, req: HttpRequest) -> impl Respon
         tracing::warn!("用户登出时未携带 refresh_token");
     }
 
-    let is_prod = app_state.configuration.is_production;
-    let expired_cookie = |name: String| {
+    clear_auth_cookies(app_state.configuration.is_production)
+}
+
+/// 返回 204 并让浏览器删除 access_token / refresh_token 两个 Cookie
+pub(crate) fn clear_auth_cookies(is_prod: bool) -> HttpResponse {
+    let expired_cookie = |name: &'static str| {
         Cookie::build(name, "")
             .path("/")
             .http_only(true)
@@ -211,11 +264,23 @@ async fn logout(app_state: web::Data<AppState>, req: HttpRequest) -> impl Respon
             .finish()
     };
 
-    let access_cookie = expired_cookie("access_token".to_string());
-    let refresh_cookie = expired_cookie("refresh_token".to_string());
-
     HttpResponse::NoContent()
-        .cookie(access_cookie)
-        .cookie(refresh_cookie)
+        .cookie(expired_cookie(ACCESS_TOKEN))
+        .cookie(expired_cookie(REFRESH_TOKEN))
         .finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_locked;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn is_locked_only_before_locked_until() {
+        let now = Utc::now();
+
+        assert!(!is_locked(None, now));
+        assert!(is_locked(Some(now + Duration::minutes(1)), now));
+        assert!(!is_locked(Some(now - Duration::minutes(1)), now));
+    }
+}
@@ -1,6 +1,7 @@
-use crate::common::AppState;
-use actix_web::{HttpResponse, Responder, post, web};
+use crate::common::{AppState, ClientInfo, send_account_email};
+use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
 use common::api::ApiResponse;
+use common::po::{EmailTokenPurpose, UserEmailTarget};
 use common::utils::{CommonUser, generate_jwt, generate_refresh_token};
 use common::{ACCESS_TOKEN, REFRESH_TOKEN};
 use serde::{Deserialize, Serialize};
@@ -9,7 +10,7 @@ use tracing::error;
 fn token_window_days(
     app_state: &web::Data<AppState>,
     token_key: &'static str,
-) -> Result<i64, HttpResponse> {
+) -> Result<i64, Box<HttpResponse>> {
     app_state
         .configuration
         .token
@@ -17,7 +18,9 @@ fn token_window_days(
         .copied()
         .ok_or_else(|| {
             error!("token 配置缺失: {token_key}");
-            HttpResponse::InternalServerError().json(ApiResponse::<()>::err("服务器内部错误"))
+            Box::new(
+                HttpResponse::InternalServerError().json(ApiResponse::<()>::err("服务器内部错误")),
+            )
         })
 }
 
@@ -36,6 +39,7 @@ struct RegisterResponse {
 
 #[post("/register")]
 pub async fn register(
+    http_req: HttpRequest,
     app_state: web::Data<AppState>,
     body: web::Json<RegisterRequest>,
 ) -> impl Responder {
@@ -116,11 +120,11 @@ pub async fn register(
             };
             let access_token_mins = match token_window_days(&app_state, ACCESS_TOKEN) {
                 Ok(v) => v,
-                Err(resp) => return resp,
+                Err(resp) => return *resp,
             };
             let refresh_token_days = match token_window_days(&app_state, REFRESH_TOKEN) {
                 Ok(v) => v,
-                Err(resp) => return resp,
+                Err(resp) => return *resp,
             };
 
             let access_token = match generate_jwt(&common_user, access_token_mins) {
@@ -141,16 +145,20 @@ pub async fn register(
             };
 
             // 持久化 refresh_token
+            let client = ClientInfo::from_request(&http_req);
             let persist_result = sqlx::query(
                 r#"
-                INSERT INTO refresh_tokens (user_id, token, expires_at, session_expires_at)
-                VALUES ($1, $2, $3, $4)
+                INSERT INTO refresh_tokens
+                    (user_id, token, expires_at, session_expires_at, user_agent, ip_address)
+                VALUES ($1, $2, $3, $4, $5, $6)
                 "#,
             )
             .bind(user_id)
             .bind(&refresh_token.token)
             .bind(refresh_token.expires_at)
             .bind(refresh_token.expires_at) // session 与 token 同周期
+            .bind(client.user_agent)
+            .bind(client.ip_address)
             .execute(&app_state.db_pool)
             .await;
 
@@ -160,6 +168,21 @@ pub async fn register(
                     .json(ApiResponse::<()>::err("服务器内部错误"));
             }
 
+            // 填写了邮箱时发送验证邮件，发送失败不影响注册结果
+            if let Some(email) = email {
+                let target = UserEmailTarget {
+                    id: user_id,
+                    username: Some(username.to_string()),
+                    email: email.to_string(),
+                    email_verified_at: None,
+                };
+                if let Err(e) =
+                    send_account_email(&app_state, &target, EmailTokenPurpose::VerifyEmail).await
+                {
+                    error!("发送邮箱验证邮件失败: {e}");
+                }
+            }
+
             let is_prod = app_state.configuration.is_production;
             let access_cookie = actix_web::cookie::Cookie::build(ACCESS_TOKEN, access_token.token)
                 .http_only(true)
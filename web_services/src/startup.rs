@@ -1,19 +1,31 @@
 use crate::common::AppState;
-use crate::middleware::{AuthMiddleware, CharsetMiddleware};
+use crate::middleware::{ADMIN_PERMISSION, AuthMiddleware, CharsetMiddleware, RequirePermission};
 use crate::routes::register::register;
 use crate::routes::{
     ani_collect_create, ani_collect_delete, ani_collect_list, ani_collect_watched, health, index,
 };
-use crate::routes::{auth_github_callback, auth_github_login, auth_token_refresh};
-use crate::routes::{get_ani, get_anis};
+use crate::routes::{
+    auth_email_verify, auth_github_callback, auth_github_login, auth_password_forgot,
+    auth_password_reset, auth_token_refresh,
+};
+use crate::routes::{get_ani, get_anis, health_checks_get};
 use crate::routes::{
     login, logout, news_event_items_get, news_events_get, news_get, news_items_get,
-    news_stream_sse, proxy_image, scheduled_tasks_create, scheduled_tasks_delete,
-    scheduled_tasks_get, scheduled_tasks_toggle, scheduled_tasks_update, task_reload,
+    news_stream_sse, proxy_image, scheduled_task_commands_get, scheduled_task_run_get,
+    scheduled_task_run_now, scheduled_task_runs_get, scheduled_tasks_create,
+    scheduled_tasks_delete, scheduled_tasks_get, scheduled_tasks_preview, scheduled_tasks_toggle,
+    scheduled_tasks_update, scheduler_status_get, task_reload, task_reload_post,
+};
+use crate::routes::{
+    me, me_email_verification_send, me_session_delete, me_sessions_delete, me_sessions_get,
+    sync_me_get, sync_me_post, sync_task_source,
+};
+use crate::routes::{
+    news_sources_create, news_sources_delete, news_sources_list, news_sources_update,
+    user_token_version_bump, user_unlock,
 };
-use crate::routes::{me, sync_me_get, sync_me_post, sync_task_source};
 use actix_web::dev::Server;
-use actix_web::{App, HttpServer, web};
+use actix_web::{App, HttpServer, guard, web};
 use anyhow::{Context, Result};
 use infra::{OAuthConfig, Setting, configure_cors, create_oauth_client, try_create_oauth_config};
 use oauth2::basic::BasicClient;
@@ -107,23 +119,52 @@ async fn create_server(
             .service(logout)
             .service(register)
             .service(auth_token_refresh)
+            .service(auth_email_verify)
+            .service(auth_password_forgot)
+            .service(auth_password_reset)
             // SSE 公开接口（无需认证，供落地页实时新闻使用）
-            // 需要认证的 API 路由
+            // 需要认证的 API 路由，按权限划分子 scope；带前缀的子 scope 须在空前缀 scope 之前注册
             .service(
                 web::scope("/api").service(news_stream_sse).service(
                     web::scope("")
                         .wrap(AuthMiddleware)
+                        .service(
+                            web::scope("/scheduledTasks")
+                                // 读取类请求（GET）需要 task:read，其余需要 task:write
+                                .service(
+                                    web::scope("")
+                                        .guard(guard::Get())
+                                        .wrap(RequirePermission("task:read"))
+                                        .service(scheduled_tasks_get)
+                                        .service(scheduled_task_commands_get)
+                                        .service(scheduled_task_runs_get)
+                                        .service(scheduled_task_run_get),
+                                )
+                                .service(
+                                    web::scope("")
+                                        .wrap(RequirePermission("task:write"))
+                                        .service(scheduled_tasks_preview)
+                                        .service(scheduled_tasks_create)
+                                        .service(scheduled_tasks_update)
+                                        .service(scheduled_tasks_toggle)
+                                        .service(scheduled_tasks_delete)
+                                        .service(scheduled_task_run_now),
+                                ),
+                        )
+                        .service(
+                            web::scope("/sync/task_source")
+                                .wrap(RequirePermission("task:write"))
+                                .service(sync_task_source),
+                        )
                         .service(me)
-                        .service(sync_task_source)
+                        .service(me_sessions_get)
+                        .service(me_session_delete)
+                        .service(me_sessions_delete)
+                        .service(me_email_verification_send)
                         .service(sync_me_get)
                         .service(sync_me_post)
                         .service(proxy_image)
                         .service(news_get)
-                        .service(scheduled_tasks_get)
-                        .service(scheduled_tasks_create)
-                        .service(scheduled_tasks_update)
-                        .service(scheduled_tasks_toggle)
-                        .service(scheduled_tasks_delete)
                         .service(ani_collect_list)
                         .service(ani_collect_create)
                         .service(ani_collect_delete)
@@ -131,6 +172,7 @@ async fn create_server(
                         .service(news_items_get)
                         .service(news_events_get)
                         .service(news_event_items_get)
+                        .service(health_checks_get)
                         .route("/anis", web::get().to(get_anis))
                         .route("/anis/{id}", web::get().to(get_ani)),
                 ),
@@ -138,7 +180,23 @@ async fn create_server(
             .service(
                 web::scope("/admin")
                     .wrap(AuthMiddleware)
-                    .service(task_reload),
+                    .service(
+                        web::scope("/news-sources")
+                            .wrap(RequirePermission("news:curate"))
+                            .service(news_sources_list)
+                            .service(news_sources_create)
+                            .service(news_sources_update)
+                            .service(news_sources_delete),
+                    )
+                    .service(
+                        web::scope("")
+                            .wrap(RequirePermission(ADMIN_PERMISSION))
+                            .service(task_reload)
+                            .service(task_reload_post)
+                            .service(scheduler_status_get)
+                            .service(user_token_version_bump)
+                            .service(user_unlock),
+                    ),
             );
 
         // 仅当 GitHub OAuth 已配置时注册对应路由